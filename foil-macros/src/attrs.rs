@@ -1,7 +1,11 @@
+use quote::quote;
 use std::collections::HashMap;
-use syn::{spanned::Spanned, Attribute, Error, Ident, Lit, Meta, MetaList, NestedMeta, Result};
+use syn::{
+    parse2, spanned::Spanned, Attribute, Error, Ident, Lit, Meta, MetaList, NestedMeta, Result,
+    Type,
+};
 
-pub struct Attrs(HashMap<Ident, Meta>);
+pub struct Attrs(HashMap<Ident, Vec<Meta>>);
 
 impl Attrs {
     pub fn extract(input: Vec<Attribute>) -> Result<Self> {
@@ -26,7 +30,7 @@ impl Attrs {
     where
         Ident: PartialEq<P>,
     {
-        if let Some(meta) = self.remove(path) {
+        if let Some(meta) = self.remove(path)? {
             if let Meta::Path(_) = meta {
                 Ok(true)
             } else {
@@ -42,7 +46,7 @@ impl Attrs {
         Ident: PartialEq<P>,
     {
         let mut attrs = HashMap::new();
-        if let Some(meta) = self.remove(path) {
+        if let Some(meta) = self.remove(path)? {
             if let Meta::List(meta_list) = meta {
                 collect_meta_list_to_map(meta_list, &mut attrs)?;
                 Ok(Some(Self(attrs)))
@@ -58,7 +62,7 @@ impl Attrs {
     where
         Ident: PartialEq<P>,
     {
-        if let Some(meta) = self.remove(path) {
+        if let Some(meta) = self.remove(path)? {
             if let Meta::NameValue(meta_name_value) = meta {
                 Ok(Some(meta_name_value.lit))
             } else {
@@ -69,36 +73,101 @@ impl Attrs {
         }
     }
 
+    pub fn get_all_lists<P: ?Sized>(&mut self, path: &P) -> Result<Vec<MetaList>>
+    where
+        Ident: PartialEq<P>,
+    {
+        self.remove_all(path)
+            .into_iter()
+            .map(|meta| {
+                if let Meta::List(meta_list) = meta {
+                    Ok(meta_list)
+                } else {
+                    Err(Error::new(meta.span(), "expected `MetaList`"))
+                }
+            })
+            .collect()
+    }
+
+    pub fn get_dbs(&mut self) -> Result<Vec<Type>> {
+        let lit_str = match self.get_name_value("db")? {
+            Some(Lit::Str(lit_str)) => lit_str,
+            Some(lit) => return Err(Error::new(lit.span(), "expected string literal")),
+            None => return Ok(dbs!()),
+        };
+
+        lit_str
+            .value()
+            .split(',')
+            .map(str::trim)
+            .map(|name| {
+                let db = match name {
+                    #[cfg(feature = "mysql")]
+                    "mysql" => quote! { ::sqlx::MySql },
+                    #[cfg(feature = "postgres")]
+                    "postgres" => quote! { ::sqlx::Postgres },
+                    #[cfg(feature = "sqlite")]
+                    "sqlite" => quote! { ::sqlx::Sqlite },
+                    #[cfg(feature = "any")]
+                    "any" => quote! { ::sqlx::Any },
+                    _ => {
+                        return Err(Error::new(
+                            lit_str.span(),
+                            format!("database {name} is not supported or not enabled"),
+                        ))
+                    }
+                };
+
+                Ok(parse2(db).unwrap())
+            })
+            .collect()
+    }
+
     pub fn ignore<P: ?Sized>(&mut self, paths: &[&P])
     where
         Ident: PartialEq<P>,
     {
         for path in paths {
-            self.remove(path);
+            self.remove_all(path);
         }
     }
 
     pub fn done(&self) -> Result<()> {
-        if let Some((_, meta)) = self.0.iter().next() {
+        if let Some(meta) = self.0.values().flatten().next() {
             Err(Error::new(meta.span(), "unexpected attribute"))
         } else {
             Ok(())
         }
     }
 
-    fn remove<P: ?Sized>(&mut self, path: &P) -> Option<Meta>
+    fn remove<P: ?Sized>(&mut self, path: &P) -> Result<Option<Meta>>
+    where
+        Ident: PartialEq<P>,
+    {
+        let mut metas = self.remove_all(path);
+        if let Some(duplicate) = metas.get(1) {
+            Err(Error::new(duplicate.span(), "duplicate attribute"))
+        } else {
+            Ok(metas.pop())
+        }
+    }
+
+    fn remove_all<P: ?Sized>(&mut self, path: &P) -> Vec<Meta>
     where
         Ident: PartialEq<P>,
     {
         if let Some(key) = self.0.keys().find(|key| *key == path).cloned() {
-            Some(self.0.remove(&key).unwrap())
+            self.0.remove(&key).unwrap()
         } else {
-            None
+            Vec::new()
         }
     }
 }
 
-fn collect_meta_list_to_map(meta_list: MetaList, map: &mut HashMap<Ident, Meta>) -> Result<()> {
+fn collect_meta_list_to_map(
+    meta_list: MetaList,
+    map: &mut HashMap<Ident, Vec<Meta>>,
+) -> Result<()> {
     for nested in meta_list.nested {
         if let NestedMeta::Meta(meta) = nested {
             let path = meta.path();
@@ -107,7 +176,7 @@ fn collect_meta_list_to_map(meta_list: MetaList, map: &mut HashMap<Ident, Meta>)
                 .get_ident()
                 .ok_or_else(|| Error::new(path.span(), "expected `Ident`"))?
                 .clone();
-            map.insert(ident, meta);
+            map.entry(ident).or_default().push(meta);
         } else {
             return Err(Error::new(nested.span(), "expected `Meta`"));
         }
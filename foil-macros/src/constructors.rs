@@ -71,6 +71,8 @@ enum FindOperator {
     Ne(NeOperator),
     In(InOperator),
     NotIn(NotInOperator),
+    Value(ValueOperator),
+    Between(BetweenOperator),
 }
 
 impl Parse for FindOperator {
@@ -82,6 +84,13 @@ impl Parse for FindOperator {
                 input.parse().map(Self::In)
             } else if ident == "NOT_IN" {
                 input.parse().map(Self::NotIn)
+            } else if VALUE_OPERATORS
+                .iter()
+                .any(|value_operator| ident == value_operator)
+            {
+                input.parse().map(Self::Value)
+            } else if ident == "BETWEEN" {
+                input.parse().map(Self::Between)
             } else {
                 input.parse().map(Self::Eq)
             }
@@ -171,6 +180,68 @@ impl Parse for NotInOperator {
     }
 }
 
+const VALUE_OPERATORS: [&str; 7] = ["LT", "LE", "GT", "GE", "LIKE", "NOT_LIKE", "ILIKE"];
+
+#[derive(Clone)]
+struct ValueOperator {
+    ident: Ident,
+    #[allow(dead_code)]
+    paren: Paren,
+    value: Expr,
+}
+
+impl Parse for ValueOperator {
+    #[allow(clippy::eval_order_dependence)]
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if !VALUE_OPERATORS
+            .iter()
+            .any(|value_operator| ident == value_operator)
+        {
+            return Err(Error::new(
+                ident.span(),
+                "expected LT, LE, GT, GE, LIKE, NOT_LIKE or ILIKE",
+            ));
+        }
+        let content;
+        Ok(Self {
+            ident,
+            paren: parenthesized!(content in input),
+            value: content.parse()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+struct BetweenOperator {
+    #[allow(dead_code)]
+    ident: Ident,
+    #[allow(dead_code)]
+    paren: Paren,
+    low: Expr,
+    #[allow(dead_code)]
+    comma: Token![,],
+    high: Expr,
+}
+
+impl Parse for BetweenOperator {
+    #[allow(clippy::eval_order_dependence)]
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if ident != "BETWEEN" {
+            return Err(Error::new(ident.span(), "expected BETWEEN"));
+        }
+        let content;
+        Ok(Self {
+            ident,
+            paren: parenthesized!(content in input),
+            low: content.parse()?,
+            comma: content.parse()?,
+            high: content.parse()?,
+        })
+    }
+}
+
 #[derive(Clone)]
 enum InValues {
     List {
@@ -256,7 +327,7 @@ pub fn expand_selector(input: SelectorInput) -> TokenStream {
                 InValues::List { paren: _, values } => {
                     let values = values.iter();
                     quote! {
-                        ::foil::manager::FindOperator::In(::std::vec![
+                        ::foil::manager::FindOperator::NotIn(::std::vec![
                             #(
                                 #values
                             ),*
@@ -269,10 +340,28 @@ pub fn expand_selector(input: SelectorInput) -> TokenStream {
                     expr,
                 } => {
                     quote! {
-                        ::foil::manager::FindOperator::In(#expr)
+                        ::foil::manager::FindOperator::NotIn(#expr)
                     }
                 }
             },
+            FindOperator::Value(value_operator) => {
+                let variant = match value_operator.ident.to_string().as_str() {
+                    "LT" => quote! { Lt },
+                    "LE" => quote! { Le },
+                    "GT" => quote! { Gt },
+                    "GE" => quote! { Ge },
+                    "LIKE" => quote! { Like },
+                    "NOT_LIKE" => quote! { NotLike },
+                    _ => quote! { ILike },
+                };
+                let value = &value_operator.value;
+                quote! { ::foil::manager::FindOperator::#variant(#value) }
+            }
+            FindOperator::Between(between_operator) => {
+                let low = &between_operator.low;
+                let high = &between_operator.high;
+                quote! { ::foil::manager::FindOperator::Between(#low, #high) }
+            }
         }
     });
 
@@ -11,27 +11,36 @@ use syn::{
 };
 
 pub fn derive_create(input: DeriveInput) -> Result<TokenStream> {
-    let dbs = dbs!();
     let config = extract_config(input)?;
+    let dbs = &config.dbs;
 
     let create = dbs
         .iter()
         .map(|db| expand_create(db, &config))
         .collect::<TokenStream>();
 
-    let input = expand_input(&dbs, &config);
+    let input = expand_input(dbs, &config);
+
+    let bulk = if config.bulk {
+        expand_bulk_create(&config)
+    } else {
+        TokenStream::new()
+    };
 
     Ok(quote! {
         #create
         #input
+        #bulk
     })
 }
 
 struct Config {
+    dbs: Vec<Type>,
     entity_ident: Ident,
     vis: Visibility,
     input_ident: Ident,
     input_is_generic: bool,
+    bulk: bool,
     fields: Vec<FieldConfig>,
 }
 
@@ -52,6 +61,8 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
 
     let mut attrs = Attrs::extract(input.attrs)?;
 
+    let dbs = attrs.get_dbs()?;
+
     let (id_field_name, id_field_name_span) = attrs
         .get_name_value("id_field")?
         .map(|lit| {
@@ -91,7 +102,16 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
         }
     }
 
-    attrs.ignore(&["table"]);
+    let bulk = attrs.get_path("bulk")?;
+
+    if bulk && !dbs.iter().any(is_postgres) {
+        return Err(Error::new(
+            input_span,
+            "bulk inserts are only supported for postgres",
+        ));
+    }
+
+    attrs.ignore(&["table", "belongs_to", "has_many", "many_to_many"]);
     attrs.done()?;
 
     if let Data::Struct(DataStruct {
@@ -128,10 +148,12 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
             .any(|field_config| contains_q_lifetime(&field_config.input_ty));
 
         Ok(Config {
+            dbs,
             entity_ident,
             vis,
             input_ident,
             input_is_generic,
+            bulk,
             fields,
         })
     } else {
@@ -188,6 +210,11 @@ fn expand_create(db: &Type, config: &Config) -> TokenStream {
     });
     let field_names = config.fields.iter().map(|field_config| &field_config.name);
     let construct_field_exprs = config.fields.iter().map(expand_construct_field_expr);
+    let unnest_columns = if config.bulk && is_postgres(db) {
+        expand_unnest_columns(config)
+    } else {
+        TokenStream::new()
+    };
 
     quote! {
         #[automatically_derived]
@@ -212,6 +239,72 @@ fn expand_create(db: &Type, config: &Config) -> TokenStream {
                     ),*
                 })
             }
+
+            #unnest_columns
+        }
+    }
+}
+
+fn expand_unnest_columns(config: &Config) -> TokenStream {
+    let entries = config.fields.iter().map(|field_config| {
+        let field_name = &field_config.name;
+        let col_name = &field_config.col_name;
+        let ty = &field_config.ty;
+
+        if field_config.generated {
+            let owned_expr = expand_owned_expr(field_config, &quote! { val });
+
+            quote! {
+                if inputs.iter().all(|input| ::std::matches!(input.#field_name, ::foil::entity::Field::Set(_))) {
+                    columns.add_col(
+                        #col_name,
+                        ::std::boxed::Box::new(
+                            inputs
+                                .iter()
+                                .filter_map(|input| {
+                                    if let ::foil::entity::Field::Set(val) = input.#field_name {
+                                        ::std::option::Option::Some(#owned_expr)
+                                    } else {
+                                        ::std::option::Option::None
+                                    }
+                                })
+                                .collect::<::std::vec::Vec<#ty>>(),
+                        ),
+                    );
+                } else if inputs.iter().any(|input| ::std::matches!(input.#field_name, ::foil::entity::Field::Set(_))) {
+                    return ::std::option::Option::None;
+                }
+            }
+        } else {
+            let owned_expr = expand_owned_expr(field_config, &quote! { input.#field_name });
+
+            quote! {
+                columns.add_col(
+                    #col_name,
+                    ::std::boxed::Box::new(
+                        inputs
+                            .iter()
+                            .map(|input| #owned_expr)
+                            .collect::<::std::vec::Vec<#ty>>(),
+                    ),
+                );
+            }
+        }
+    });
+
+    quote! {
+        fn unnest_columns<'q>(
+            inputs: &[Self::Input<'q>],
+        ) -> ::std::option::Option<::foil::manager::InputRecord<'q, ::sqlx::Postgres>> {
+            if inputs.len() < 2 {
+                return ::std::option::Option::None;
+            }
+
+            let mut columns = ::foil::manager::InputRecord::new();
+            #(
+                #entries
+            )*
+            ::std::option::Option::Some(columns)
         }
     }
 }
@@ -227,7 +320,23 @@ fn expand_construct_field_expr(field_config: &FieldConfig) -> TokenStream {
         quote! { input.#field_name }
     };
 
-    let owned_expr = if field_config.ty == field_config.input_ty {
+    let owned_expr = expand_owned_expr(field_config, &alias);
+
+    if generated {
+        quote! {
+            if let ::foil::entity::Field::Set(val) = input.#field_name {
+                #owned_expr
+            } else {
+                generated.col(#col_name)?
+            }
+        }
+    } else {
+        owned_expr
+    }
+}
+
+fn expand_owned_expr(field_config: &FieldConfig, alias: &TokenStream) -> TokenStream {
+    if field_config.ty == field_config.input_ty {
         quote! { #alias }
     } else {
         let mut unwrapped_input_ty = field_config.input_ty.clone();
@@ -241,18 +350,6 @@ fn expand_construct_field_expr(field_config: &FieldConfig) -> TokenStream {
         } else {
             quote! { ::std::borrow::ToOwned::to_owned(#alias)}
         }
-    };
-
-    if generated {
-        quote! {
-            if let ::foil::entity::Field::Set(val) = input.#field_name {
-                #owned_expr
-            } else {
-                generated.col(#col_name)?
-            }
-        }
-    } else {
-        owned_expr
     }
 }
 
@@ -373,3 +470,49 @@ fn expand_to_input_record_entry(field_config: &FieldConfig) -> TokenStream {
 
     entry
 }
+
+fn expand_bulk_create(config: &Config) -> TokenStream {
+    let entity_ident = &config.entity_ident;
+    let (generated, copied): (Vec<_>, Vec<_>) = config
+        .fields
+        .iter()
+        .partition(|field_config| field_config.generated);
+    let generated_field_names = generated.iter().map(|field_config| &field_config.name);
+    let generated_col_names = generated.iter().map(|field_config| &field_config.col_name);
+    let copy_field_names = copied.iter().map(|field_config| &field_config.name);
+    let copy_col_names = copied.iter().map(|field_config| &field_config.col_name);
+
+    quote! {
+        #[automatically_derived]
+        impl ::foil::entity::BulkCreate for #entity_ident {
+            fn copy_col_names() -> &'static [&'static str] {
+                &[
+                    #(
+                        #copy_col_names
+                    ),*
+                ]
+            }
+
+            fn write_copy_row(
+                input: &Self::Input<'_>,
+                row: &mut ::foil::entity::CopyRow,
+            ) -> ::std::result::Result<(), ::foil::entity::CopyError> {
+                #(
+                    if let ::foil::entity::Field::Set(_) = input.#generated_field_names {
+                        return ::std::result::Result::Err(
+                            ::foil::entity::CopyError::GeneratedColSet(#generated_col_names),
+                        );
+                    }
+                )*
+                #(
+                    row.field(&input.#copy_field_names)?;
+                )*
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+fn is_postgres(db: &Type) -> bool {
+    quote! { #db }.to_string() == quote! { ::sqlx::Postgres }.to_string()
+}
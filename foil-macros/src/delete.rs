@@ -1,15 +1,16 @@
+use crate::attrs::Attrs;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse2, DeriveInput, Type};
+use syn::{DeriveInput, Result};
 
-pub fn derive_delete(input: &DeriveInput) -> TokenStream {
-    let dbs = dbs!();
+pub fn derive_delete(input: DeriveInput) -> Result<TokenStream> {
+    let dbs = Attrs::extract(input.attrs)?.get_dbs()?;
     let entity_ident = &input.ident;
 
-    quote! {
+    Ok(quote! {
         #(
             #[automatically_derived]
             impl ::foil::entity::Delete<#dbs> for #entity_ident {}
         )*
-    }
+    })
 }
@@ -9,12 +9,16 @@ use syn::{
 
 use crate::{
     attrs::Attrs,
+    relations::{
+        expand_many_to_many, expand_relation, extract_relation_configs, RelationConfig,
+        RelationKind,
+    },
     types::{contains_q_lifetime, into_input_type},
 };
 
 pub fn derive_entity(input: DeriveInput) -> Result<TokenStream> {
-    let dbs = [parse2(TokenStream::from_str("::sqlx::Postgres").unwrap()).unwrap()];
     let config = extract_config(input)?;
+    let dbs = &config.dbs;
 
     let entity = dbs
         .iter()
@@ -28,9 +32,11 @@ pub fn derive_entity(input: DeriveInput) -> Result<TokenStream> {
 
     let col = expand_col(&config);
 
-    let selector = expand_selector(&dbs, &config);
+    let selector = expand_selector(dbs, &config);
 
-    let lazy_columns = expand_lazy_columns(&config);
+    let lazy_columns = expand_lazy_columns(dbs, &config);
+
+    let relations = expand_relations(&config);
 
     Ok(quote! {
         #entity
@@ -38,18 +44,22 @@ pub fn derive_entity(input: DeriveInput) -> Result<TokenStream> {
         #col
         #selector
         #lazy_columns
+        #relations
     })
 }
 
 struct Config {
+    dbs: Vec<Type>,
     entity_ident: Ident,
     vis: Visibility,
     col_ident: Ident,
     selector_ident: Ident,
+    lazy_ident: Ident,
     selector_is_generic: bool,
     table_name: LitStr,
     id_field_name: Ident,
     fields: Vec<FieldConfig>,
+    relations: Vec<RelationConfig>,
 }
 
 struct FieldConfig {
@@ -60,6 +70,7 @@ struct FieldConfig {
     is_lazy: bool,
 }
 
+#[allow(clippy::too_many_lines)]
 fn extract_config(input: DeriveInput) -> Result<Config> {
     let input_span = input.span();
     let entity_ident = input.ident;
@@ -69,10 +80,14 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
 
     let mut attrs = Attrs::extract(input.attrs)?;
 
+    let dbs = attrs.get_dbs()?;
+
     let col_ident = Ident::new(&format!("{}Col", entity_ident), Span::call_site());
 
     let selector_ident = Ident::new(&format!("{}Selector", entity_ident), Span::call_site());
 
+    let lazy_ident = Ident::new(&format!("{entity_ident}Lazy"), Span::call_site());
+
     let table_name = attrs
         .get_name_value("table")?
         .map(|lit| {
@@ -126,6 +141,9 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
         }
     }
 
+    let relations = extract_relation_configs(&mut attrs)?;
+
+    attrs.ignore(&["bulk"]);
     attrs.done()?;
 
     if let Data::Struct(DataStruct {
@@ -154,25 +172,47 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
             ));
         }
 
+        check_relations(&relations, &fields)?;
+
         let selector_is_generic = fields
             .iter()
             .any(|field_config| contains_q_lifetime(&field_config.input_ty));
 
         Ok(Config {
+            dbs,
             entity_ident,
             vis,
             col_ident,
             selector_ident,
+            lazy_ident,
             selector_is_generic,
             table_name,
             id_field_name,
             fields,
+            relations,
         })
     } else {
         Err(Error::new(input_span, "expected struct with named fields"))
     }
 }
 
+fn check_relations(relations: &[RelationConfig], fields: &[FieldConfig]) -> Result<()> {
+    for relation in relations {
+        if let RelationKind::BelongsTo = relation.kind {
+            if !fields.iter().any(|field_config| {
+                !field_config.is_lazy && field_config.col_name.value() == relation.fk.value()
+            }) {
+                return Err(Error::new(
+                    relation.fk.span(),
+                    format!("column {} does not exist", relation.fk.value()),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn extract_field_config(
     name: Ident,
     ty: Type,
@@ -267,14 +307,13 @@ fn expand_entity(db: &Type, config: &Config) -> TokenStream {
 
 fn expand_from_record(db: &Type, config: &Config) -> TokenStream {
     let entity_ident = &config.entity_ident;
-    let field_names = config.fields.iter().map(|field_config| &field_config.name);
-    let col_names = config.fields.iter().filter_map(|field_config| {
-        if field_config.is_lazy {
-            None
-        } else {
-            Some(&field_config.col_name)
-        }
-    });
+    let fields = config
+        .fields
+        .iter()
+        .filter(|field_config| !field_config.is_lazy)
+        .collect::<Vec<_>>();
+    let field_names = fields.iter().map(|field_config| &field_config.name);
+    let col_names = fields.iter().map(|field_config| &field_config.col_name);
 
     quote! {
         #[automatically_derived]
@@ -372,7 +411,7 @@ fn expand_selector(dbs: &[Type], config: &Config) -> TokenStream {
         .collect::<TokenStream>();
 
     quote! {
-        #[derive(::std::default::Default)]
+        #[derive(::std::clone::Clone, ::std::default::Default)]
         #vis struct #selector_type {
             #(
                 pub #field_names: ::foil::entity::Field<::foil::manager::FindOperator<#selector_field_types>>
@@ -383,20 +422,39 @@ fn expand_selector(dbs: &[Type], config: &Config) -> TokenStream {
     }
 }
 
-fn expand_lazy_columns(config: &Config) -> TokenStream {
+fn expand_lazy_columns(dbs: &[Type], config: &Config) -> TokenStream {
     let entity_ident = &config.entity_ident;
     let lazy_fields = config
         .fields
         .iter()
         .filter(|field_config| field_config.is_lazy)
         .collect::<Vec<_>>();
-    let field_names = lazy_fields.iter().map(|field_config| &field_config.name);
-    let field_types = lazy_fields.iter().map(|field_config| &field_config.ty);
+
+    if lazy_fields.is_empty() {
+        return TokenStream::new();
+    }
+
+    let field_names = lazy_fields
+        .iter()
+        .map(|field_config| &field_config.name)
+        .collect::<Vec<_>>();
+    let load_method_names = field_names
+        .iter()
+        .map(|field_name| Ident::new(&format!("load_{field_name}"), field_name.span()));
+    let field_types = lazy_fields
+        .iter()
+        .map(|field_config| &field_config.ty)
+        .collect::<Vec<_>>();
     let col_names = lazy_fields
         .iter()
-        .map(|field_config| &field_config.col_name);
+        .map(|field_config| &field_config.col_name)
+        .collect::<Vec<_>>();
+
+    let lazy_struct = expand_lazy_struct(dbs, config, &lazy_fields);
 
     quote! {
+        #lazy_struct
+
         #[automatically_derived]
         impl #entity_ident {
             #(
@@ -411,34 +469,135 @@ fn expand_lazy_columns(config: &Config) -> TokenStream {
                 ) -> ::std::result::Result<#field_types, ::foil::entity::SelectOneError<E>>
                 where
                     Self: ::foil::entity::Entity<DB>,
-                    str: ::sqlx::ColumnIndex<<DB as ::sqlx::Database>::Row>,
+                    <Self as ::foil::entity::Entity<DB>>::Id: ::sqlx::Type<DB>
+                        + for<'d> ::sqlx::Decode<'d, DB>
+                        + ::std::cmp::Eq
+                        + ::std::hash::Hash
+                        + ::std::any::Any,
+                    for<'a> &'a str: ::sqlx::ColumnIndex<<DB as ::sqlx::Database>::Row>,
                     #field_types: ::sqlx::Type<DB> + for<'d> ::sqlx::Decode<'d, DB>,
                 {
-                    let mut selector = ::foil::manager::Selector::new();
-                    selector.add_col("id", ::foil::manager::FindOperator::Eq(Box::new(self.id())));
-                    let query = ::foil::manager::SelectQuery::<DB> {
-                        table_name: <Self as ::foil::entity::Entity<DB>>::table_name(),
-                        col_names: &[#col_names],
-                        selectors: vec![selector],
-                        order_by: None,
-                        offset: None,
-                        limit: None,
-                    };
-
-                    let record = manager
-                        .select(query)
-                        .try_next()
-                        .await
-                        .map_err(::foil::entity::SelectError::Manager)?
+                    let values = <Self as ::foil::entity::Entity<DB>>::load_lazy(
+                        manager,
+                        #col_names,
+                        ::std::vec![::foil::entity::Entity::<DB>::id(self)],
+                    )
+                    .await?;
+
+                    let (_, value) = ::std::iter::IntoIterator::into_iter(values)
+                        .next()
                         .ok_or(::foil::entity::SelectOneError::RowNotFound)?;
 
-                    let value = record
-                        .col(#col_names)
-                        .map_err(::foil::entity::SelectError::Record)?;
+                    ::std::result::Result::Ok(value)
+                }
 
-                    Ok(value)
+                pub fn #load_method_names<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+                    manager: M,
+                    entities: &[Self],
+                ) -> ::foil::manager::BoxFuture<
+                    'o,
+                    ::std::result::Result<
+                        ::std::collections::HashMap<<Self as ::foil::entity::Entity<DB>>::Id, #field_types>,
+                        ::foil::entity::SelectError<M::Error>,
+                    >,
+                >
+                where
+                    Self: ::foil::entity::Entity<DB>,
+                    <Self as ::foil::entity::Entity<DB>>::Id: ::sqlx::Type<DB>
+                        + for<'d> ::sqlx::Decode<'d, DB>
+                        + ::std::cmp::Eq
+                        + ::std::hash::Hash
+                        + ::std::any::Any,
+                    for<'a> &'a str: ::sqlx::ColumnIndex<<DB as ::sqlx::Database>::Row>,
+                    #field_types: ::sqlx::Type<DB> + for<'d> ::sqlx::Decode<'d, DB>,
+                {
+                    let ids = entities
+                        .iter()
+                        .map(::foil::entity::Entity::<DB>::id)
+                        .collect();
+                    let values = <Self as ::foil::entity::Entity<DB>>::load_lazy(manager, #col_names, ids);
+
+                    ::std::boxed::Box::pin(async move {
+                        let values = values.await?;
+                        ::std::result::Result::Ok(::std::iter::Iterator::collect(::std::iter::IntoIterator::into_iter(values)))
+                    })
                 }
             )*
         }
     }
 }
+
+fn expand_lazy_struct(dbs: &[Type], config: &Config, lazy_fields: &[&FieldConfig]) -> TokenStream {
+    let vis = &config.vis;
+    let lazy_ident = &config.lazy_ident;
+    let field_names = lazy_fields
+        .iter()
+        .map(|field_config| &field_config.name)
+        .collect::<Vec<_>>();
+    let field_types = lazy_fields.iter().map(|field_config| &field_config.ty);
+    let col_names = lazy_fields
+        .iter()
+        .map(|field_config| &field_config.col_name)
+        .collect::<Vec<_>>();
+
+    let from_record_impls = dbs
+        .iter()
+        .map(|db| {
+            quote! {
+                #[automatically_derived]
+                impl ::foil::manager::FromRecord<#db> for #lazy_ident {
+                    fn from_record(record: &::foil::manager::Record<#db>) -> ::std::result::Result<Self, ::foil::manager::RecordError> {
+                        ::std::result::Result::Ok(#lazy_ident {
+                            #(
+                                #field_names: record.try_col(#col_names)?
+                            ),*
+                        })
+                    }
+                }
+            }
+        })
+        .collect::<TokenStream>();
+
+    quote! {
+        #vis struct #lazy_ident {
+            #(
+                pub #field_names: ::std::option::Option<#field_types>
+            ),*
+        }
+
+        #from_record_impls
+    }
+}
+
+fn expand_relations(config: &Config) -> TokenStream {
+    config
+        .relations
+        .iter()
+        .map(|relation| {
+            if let RelationKind::ManyToMany = relation.kind {
+                return expand_many_to_many(&config.entity_ident, relation);
+            }
+
+            let key_field = config
+                .fields
+                .iter()
+                .find(|field_config| match relation.kind {
+                    RelationKind::BelongsTo => {
+                        !field_config.is_lazy
+                            && field_config.col_name.value() == relation.fk.value()
+                    }
+                    RelationKind::HasMany | RelationKind::ManyToMany => {
+                        field_config.name == config.id_field_name
+                    }
+                })
+                .unwrap();
+
+            expand_relation(
+                &config.entity_ident,
+                relation,
+                &key_field.name,
+                &key_field.ty,
+            )
+        })
+        .collect()
+}
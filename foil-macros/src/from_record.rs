@@ -0,0 +1,130 @@
+use crate::attrs::Attrs;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    spanned::Spanned, Data, DataStruct, DeriveInput, Error, Fields, Ident, Lit, LitStr, Result,
+    Type,
+};
+
+pub fn derive_from_record(input: DeriveInput) -> Result<TokenStream> {
+    let config = extract_config(input)?;
+
+    Ok(config
+        .dbs
+        .iter()
+        .map(|db| expand_from_record(db, &config))
+        .collect())
+}
+
+struct Config {
+    dbs: Vec<Type>,
+    ident: Ident,
+    fields: Vec<FieldConfig>,
+}
+
+struct FieldConfig {
+    name: Ident,
+    ty: Type,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Col { col_name: LitStr, default: bool },
+    Flatten,
+}
+
+fn extract_config(input: DeriveInput) -> Result<Config> {
+    let input_span = input.span();
+    let ident = input.ident;
+
+    let mut attrs = Attrs::extract(input.attrs)?;
+    let dbs = attrs.get_dbs()?;
+    attrs.done()?;
+
+    if let Data::Struct(DataStruct {
+        struct_token: _,
+        fields: Fields::Named(fields_named),
+        semi_token: _,
+    }) = input.data
+    {
+        let fields = fields_named
+            .named
+            .into_iter()
+            .map(|field| {
+                let name = field.ident.unwrap();
+                let mut attrs = Attrs::extract(field.attrs)?;
+
+                let kind = if attrs.get_path("flatten")? {
+                    FieldKind::Flatten
+                } else {
+                    let col_name = attrs
+                        .get_name_value("rename")?
+                        .map(|lit| {
+                            if let Lit::Str(lit_str) = lit {
+                                Ok(lit_str)
+                            } else {
+                                Err(Error::new(lit.span(), "expected string literal"))
+                            }
+                        })
+                        .transpose()?
+                        .unwrap_or_else(|| LitStr::new(&name.to_string(), Span::call_site()));
+
+                    FieldKind::Col {
+                        col_name,
+                        default: attrs.get_path("default")?,
+                    }
+                };
+
+                attrs.done()?;
+
+                Ok(FieldConfig {
+                    name,
+                    ty: field.ty,
+                    kind,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Config { dbs, ident, fields })
+    } else {
+        Err(Error::new(input_span, "expected struct with named fields"))
+    }
+}
+
+fn expand_from_record(db: &Type, config: &Config) -> TokenStream {
+    let ident = &config.ident;
+    let field_names = config.fields.iter().map(|field_config| &field_config.name);
+    let field_values = config.fields.iter().map(|field_config| {
+        let ty = &field_config.ty;
+        match &field_config.kind {
+            FieldKind::Col {
+                col_name,
+                default: false,
+            } => quote! {
+                record.col(#col_name)?
+            },
+            FieldKind::Col {
+                col_name,
+                default: true,
+            } => quote! {
+                record.try_col(#col_name)?.unwrap_or_default()
+            },
+            FieldKind::Flatten => quote! {
+                <#ty as ::foil::manager::FromRecord<#db>>::from_record(record)?
+            },
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl ::foil::manager::FromRecord<#db> for #ident {
+            fn from_record(record: &::foil::manager::Record<#db>) -> ::std::result::Result<Self, ::foil::manager::RecordError> {
+                ::std::result::Result::Ok(#ident {
+                    #(
+                        #field_names: #field_values
+                    ),*
+                })
+            }
+        }
+    }
+}
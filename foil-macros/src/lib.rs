@@ -9,6 +9,7 @@ use crate::entity::derive_entity;
 use constructors::{expand_patch, expand_selector, PatchInput, SelectorInput};
 use create::derive_create;
 use delete::derive_delete;
+use from_record::derive_from_record;
 use syn::{parse_macro_input, DeriveInput};
 use update::derive_update;
 use value::derive_value;
@@ -18,8 +19,6 @@ macro_rules! dbs {
         [
             #[cfg(feature = "mysql")]
             quote! { ::sqlx::MySql },
-            #[cfg(feature = "mssql")]
-            quote! { ::sqlx::Mssql },
             #[cfg(feature = "postgres")]
             quote! { ::sqlx::Postgres },
             #[cfg(feature = "sqlite")]
@@ -38,6 +37,7 @@ mod constructors;
 mod create;
 mod delete;
 mod entity;
+mod from_record;
 mod relations;
 mod types;
 mod update;
@@ -67,10 +67,20 @@ pub fn update(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
-#[proc_macro_derive(Delete)]
+#[proc_macro_derive(Delete, attributes(foil))]
 pub fn delete(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    derive_delete(&input).into()
+    derive_delete(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FromRecord, attributes(foil))]
+pub fn from_record(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_from_record(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 #[proc_macro_derive(Value)]
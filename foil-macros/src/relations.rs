@@ -0,0 +1,367 @@
+use heck::ToSnakeCase;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{spanned::Spanned, Error, Lit, LitStr, Meta, MetaList, NestedMeta, Path, Result, Type};
+
+use crate::attrs::Attrs;
+
+pub struct RelationConfig {
+    pub kind: RelationKind,
+    pub target: Path,
+    pub fk: LitStr,
+    pub join_table: Option<(LitStr, LitStr)>,
+    pub method_name: Ident,
+}
+
+#[derive(Clone, Copy)]
+pub enum RelationKind {
+    BelongsTo,
+    HasMany,
+    ManyToMany,
+}
+
+impl RelationKind {
+    fn attr_name(self) -> &'static str {
+        match self {
+            Self::BelongsTo => "belongs_to",
+            Self::HasMany => "has_many",
+            Self::ManyToMany => "many_to_many",
+        }
+    }
+}
+
+pub fn extract_relation_configs(attrs: &mut Attrs) -> Result<Vec<RelationConfig>> {
+    let mut configs = Vec::new();
+
+    for kind in [
+        RelationKind::BelongsTo,
+        RelationKind::HasMany,
+        RelationKind::ManyToMany,
+    ] {
+        for meta_list in attrs.get_all_lists(kind.attr_name())? {
+            configs.push(extract_relation_config(kind, meta_list)?);
+        }
+    }
+
+    Ok(configs)
+}
+
+fn extract_relation_config(kind: RelationKind, meta_list: MetaList) -> Result<RelationConfig> {
+    let span = meta_list.span();
+    let mut nested = meta_list.nested.into_iter();
+
+    let target = match nested.next() {
+        Some(NestedMeta::Meta(Meta::Path(path))) => path,
+        Some(nested) => return Err(Error::new(nested.span(), "expected entity path")),
+        None => return Err(Error::new(span, "expected entity path")),
+    };
+
+    let mut fk = None;
+    let mut name = None;
+    let mut join_table = None;
+    let mut target_fk = None;
+
+    for nested in nested {
+        if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = &nested {
+            let lit_str = if let Lit::Str(lit_str) = &meta_name_value.lit {
+                lit_str.clone()
+            } else {
+                return Err(Error::new(
+                    meta_name_value.lit.span(),
+                    "expected string literal",
+                ));
+            };
+
+            if meta_name_value.path.is_ident("fk") {
+                fk = Some(lit_str);
+                continue;
+            } else if meta_name_value.path.is_ident("name") {
+                name = Some(lit_str);
+                continue;
+            } else if let RelationKind::ManyToMany = kind {
+                if meta_name_value.path.is_ident("join_table") {
+                    join_table = Some(lit_str);
+                    continue;
+                } else if meta_name_value.path.is_ident("target_fk") {
+                    target_fk = Some(lit_str);
+                    continue;
+                }
+            }
+        }
+
+        return Err(Error::new(nested.span(), "unexpected attribute"));
+    }
+
+    let fk = fk.ok_or_else(|| Error::new(span, "fk must be specified for relation"))?;
+
+    let join_table = if let RelationKind::ManyToMany = kind {
+        Some((
+            join_table
+                .ok_or_else(|| Error::new(span, "join_table must be specified for relation"))?,
+            target_fk
+                .ok_or_else(|| Error::new(span, "target_fk must be specified for relation"))?,
+        ))
+    } else {
+        None
+    };
+
+    let method_name = if let Some(name) = name {
+        Ident::new(&name.value(), name.span())
+    } else {
+        let target_name = target
+            .segments
+            .last()
+            .ok_or_else(|| Error::new(target.span(), "expected entity path"))?
+            .ident
+            .to_string()
+            .to_snake_case();
+
+        let method_name = match kind {
+            RelationKind::BelongsTo => target_name,
+            RelationKind::HasMany | RelationKind::ManyToMany => pluralize(&target_name),
+        };
+
+        Ident::new(&method_name, target.span())
+    };
+
+    Ok(RelationConfig {
+        kind,
+        target,
+        fk,
+        join_table,
+        method_name,
+    })
+}
+
+fn pluralize(name: &str) -> String {
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        format!("{name}es")
+    } else if let Some(stem) = name.strip_suffix('y').filter(|stem| {
+        stem.chars()
+            .last()
+            .is_some_and(|last| !"aeiou".contains(last))
+    }) {
+        format!("{stem}ies")
+    } else {
+        format!("{name}s")
+    }
+}
+
+pub fn expand_relation(
+    entity_ident: &Ident,
+    relation: &RelationConfig,
+    key_field_name: &Ident,
+    key_field_ty: &Type,
+) -> TokenStream {
+    let target = &relation.target;
+    let method_name = &relation.method_name;
+
+    let target_col_name = match relation.kind {
+        RelationKind::BelongsTo => {
+            quote! { <#target as ::foil::entity::Entity<DB>>::id_col_name() }
+        }
+        RelationKind::HasMany | RelationKind::ManyToMany => {
+            let fk = &relation.fk;
+            quote! { #fk }
+        }
+    };
+
+    let outer_col_name = match relation.kind {
+        RelationKind::BelongsTo => {
+            let fk = &relation.fk;
+            quote! { #fk }
+        }
+        RelationKind::HasMany | RelationKind::ManyToMany => {
+            quote! { <Self as ::foil::entity::Entity<DB>>::id_col_name() }
+        }
+    };
+
+    let has_method_name = Ident::new(&format!("has_{method_name}"), method_name.span());
+
+    let load_method = expand_load_method(relation, key_field_name, key_field_ty, &target_col_name);
+
+    quote! {
+        #[automatically_derived]
+        impl #entity_ident {
+            #load_method
+
+            pub fn #has_method_name<'q, DB: ::sqlx::Database>(
+                selectors: ::std::vec::Vec<<#target as ::foil::entity::Entity<DB>>::Selector<'q>>,
+            ) -> ::foil::manager::Filter<'q, DB>
+            where
+                Self: ::foil::entity::Entity<DB>,
+                #target: ::foil::entity::Entity<DB>,
+            {
+                ::foil::entity::exists::<DB, Self, #target>(#outer_col_name, #target_col_name, selectors)
+            }
+
+            pub fn #method_name<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+                &self,
+                manager: M,
+            ) -> ::foil::entity::Selection<'o, M::Error, #target, DB>
+            where
+                #target: ::foil::entity::Entity<DB>,
+                #key_field_ty: ::foil::manager::Value<'o, DB> + ::std::clone::Clone + 'o,
+            {
+                let mut selector = ::foil::manager::Selector::new();
+                selector.add_col(
+                    #target_col_name,
+                    ::foil::manager::FindOperator::Eq(::std::boxed::Box::new(
+                        ::std::clone::Clone::clone(&self.#key_field_name),
+                    )),
+                );
+
+                <#target as ::foil::entity::Entity<DB>>::find_where(
+                    manager,
+                    selector.into(),
+                    ::std::default::Default::default(),
+                )
+            }
+        }
+    }
+}
+
+fn expand_load_method(
+    relation: &RelationConfig,
+    key_field_name: &Ident,
+    key_field_ty: &Type,
+    target_col_name: &TokenStream,
+) -> TokenStream {
+    let target = &relation.target;
+    let method_name = &relation.method_name;
+    let load_method_name = Ident::new(&format!("load_{method_name}"), method_name.span());
+
+    let (output_ty, collect) = match relation.kind {
+        RelationKind::BelongsTo => (
+            quote! { #target },
+            quote! { ::std::iter::Iterator::collect(::std::iter::IntoIterator::into_iter(pairs)) },
+        ),
+        RelationKind::HasMany | RelationKind::ManyToMany => (
+            quote! { ::std::vec::Vec<#target> },
+            quote! {
+                let mut map = ::std::collections::HashMap::new();
+                for (key, entity) in pairs {
+                    map.entry(key).or_insert_with(::std::vec::Vec::new).push(entity);
+                }
+                map
+            },
+        ),
+    };
+
+    quote! {
+        pub fn #load_method_name<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+            manager: M,
+            entities: &[Self],
+        ) -> ::foil::manager::BoxFuture<
+            'o,
+            ::std::result::Result<
+                ::std::collections::HashMap<#key_field_ty, #output_ty>,
+                ::foil::entity::SelectError<M::Error>,
+            >,
+        >
+        where
+            #target: ::foil::entity::Entity<DB>,
+            #key_field_ty: for<'q> ::foil::manager::Value<'q, DB>
+                + ::sqlx::Type<DB>
+                + for<'d> ::sqlx::Decode<'d, DB>
+                + ::std::clone::Clone
+                + ::std::cmp::Eq
+                + ::std::hash::Hash
+                + ::std::any::Any,
+            for<'a> &'a str: ::sqlx::ColumnIndex<<DB as ::sqlx::Database>::Row>,
+        {
+            let keys = entities
+                .iter()
+                .map(|entity| ::std::clone::Clone::clone(&entity.#key_field_name))
+                .collect();
+            let pairs = <#target as ::foil::entity::Entity<DB>>::find_by_keys(manager, #target_col_name, keys);
+
+            ::std::boxed::Box::pin(async move {
+                let pairs = pairs.await?;
+                ::std::result::Result::Ok({ #collect })
+            })
+        }
+    }
+}
+
+pub fn expand_many_to_many(entity_ident: &Ident, relation: &RelationConfig) -> TokenStream {
+    let target = &relation.target;
+    let method_name = &relation.method_name;
+    let attach_method_name = Ident::new(&format!("attach_{method_name}"), method_name.span());
+    let detach_method_name = Ident::new(&format!("detach_{method_name}"), method_name.span());
+    let sync_method_name = Ident::new(&format!("sync_{method_name}"), method_name.span());
+    let fk = &relation.fk;
+    let (join_table, target_fk) = relation.join_table.as_ref().unwrap();
+
+    let join_table = quote! {
+        ::foil::entity::JoinTable {
+            table_name: #join_table,
+            col_name: #fk,
+            target_col_name: #target_fk,
+        }
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #entity_ident {
+            pub fn #method_name<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+                &self,
+                manager: M,
+            ) -> ::foil::entity::Selection<'o, M::Error, #target, DB>
+            where
+                Self: ::foil::entity::Entity<DB>,
+                #target: ::foil::entity::Entity<DB>,
+            {
+                #join_table.find::<M, DB, Self, #target>(manager, self)
+            }
+
+            pub fn #attach_method_name<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+                &self,
+                manager: M,
+                ids: ::std::vec::Vec<<#target as ::foil::entity::Entity<DB>>::Id>,
+            ) -> ::foil::manager::BoxFuture<'o, ::std::result::Result<(), M::Error>>
+            where
+                Self: ::foil::entity::Entity<DB>,
+                #target: ::foil::entity::Entity<DB>,
+            {
+                #join_table.attach::<M, DB, Self, #target>(manager, self, ids)
+            }
+
+            pub fn #detach_method_name<'m: 'o, 'o, M: ::foil::manager::Manager<'m, DB>, DB: ::sqlx::Database>(
+                &self,
+                manager: M,
+                ids: ::std::vec::Vec<<#target as ::foil::entity::Entity<DB>>::Id>,
+            ) -> ::foil::manager::BoxFuture<'o, ::std::result::Result<u64, M::Error>>
+            where
+                Self: ::foil::entity::Entity<DB>,
+                #target: ::foil::entity::Entity<DB>,
+            {
+                #join_table.detach::<M, DB, Self, #target>(manager, self, ids)
+            }
+
+            pub fn #sync_method_name<'t, 'o, X: ::foil::manager::Transaction<'t, DB>, DB: ::sqlx::Database>(
+                &self,
+                transaction: &'o mut X,
+                ids: ::std::vec::Vec<<#target as ::foil::entity::Entity<DB>>::Id>,
+            ) -> ::foil::manager::BoxFuture<
+                'o,
+                ::std::result::Result<(), ::foil::entity::SyncError<X::Error>>,
+            >
+            where
+                Self: ::foil::entity::Entity<DB>,
+                #target: ::foil::entity::Entity<DB>,
+                <#target as ::foil::entity::Entity<DB>>::Id: ::sqlx::Type<DB>
+                    + for<'d> ::sqlx::Decode<'d, DB>
+                    + ::std::cmp::Eq
+                    + ::std::hash::Hash
+                    + ::std::any::Any,
+                for<'a> &'a str: ::sqlx::ColumnIndex<<DB as ::sqlx::Database>::Row>,
+            {
+                #join_table.sync::<X, DB, Self, #target>(transaction, self, ids)
+            }
+        }
+    }
+}
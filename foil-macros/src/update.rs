@@ -11,19 +11,16 @@ use syn::{
 };
 
 pub fn derive_update(input: DeriveInput) -> Result<TokenStream> {
-    let dbs = dbs!();
     let config = extract_config(input)?;
+    let dbs = &config.dbs;
 
     let update = dbs
         .iter()
         .map(|db| expand_update(db, &config))
         .collect::<TokenStream>();
-    let patch = expand_patch(&dbs, &config);
+    let patch = expand_patch(dbs, &config);
 
-    let setters = dbs
-        .iter()
-        .map(|db| expand_setters(db, &config))
-        .collect::<TokenStream>();
+    let setters = expand_setters(dbs, &config);
 
     Ok(quote! {
         #update
@@ -33,6 +30,7 @@ pub fn derive_update(input: DeriveInput) -> Result<TokenStream> {
 }
 
 struct Config {
+    dbs: Vec<Type>,
     entity_ident: Ident,
     vis: Visibility,
     patch_ident: Ident,
@@ -56,6 +54,8 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
 
     let mut attrs = Attrs::extract(input.attrs)?;
 
+    let dbs = attrs.get_dbs()?;
+
     if let Some(mut lazy) = attrs.get_list("lazy")? {
         let keys = lazy.keys().map(ToOwned::to_owned).collect::<Vec<_>>();
         for name in keys {
@@ -80,7 +80,14 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
         }
     }
 
-    attrs.ignore(&["table", "id_field"]);
+    attrs.ignore(&[
+        "table",
+        "id_field",
+        "belongs_to",
+        "has_many",
+        "many_to_many",
+        "bulk",
+    ]);
     attrs.done()?;
 
     if let Data::Struct(DataStruct {
@@ -105,6 +112,7 @@ fn extract_config(input: DeriveInput) -> Result<Config> {
             .any(|field_config| contains_q_lifetime(&field_config.input_ty));
 
         Ok(Config {
+            dbs,
             entity_ident,
             vis,
             patch_ident,
@@ -241,7 +249,7 @@ fn expand_patch(dbs: &[Type], config: &Config) -> TokenStream {
     }
 }
 
-fn expand_setters(db: &Type, config: &Config) -> TokenStream {
+fn expand_setters(dbs: &[Type], config: &Config) -> TokenStream {
     let entity_ident = &config.entity_ident;
     let vis = &config.vis;
     let setters_ident = Ident::new(&format!("{}Setters", entity_ident), Span::call_site());
@@ -249,10 +257,25 @@ fn expand_setters(db: &Type, config: &Config) -> TokenStream {
     let setter_signatures = config.fields.iter().map(|field_config| {
         expand_setter(config, field_config, false, &parse2(quote! { DB }).unwrap())
     });
-    let setters = config
-        .fields
+
+    let setters_impls = dbs
         .iter()
-        .map(|field_config| expand_setter(config, field_config, true, db));
+        .map(|db| {
+            let setters = config
+                .fields
+                .iter()
+                .map(|field_config| expand_setter(config, field_config, true, db));
+
+            quote! {
+                #[automatically_derived]
+                impl #setters_ident<#db> for #entity_ident {
+                    #(
+                        #setters
+                    )*
+                }
+            }
+        })
+        .collect::<TokenStream>();
 
     quote! {
         #[automatically_derived]
@@ -262,12 +285,7 @@ fn expand_setters(db: &Type, config: &Config) -> TokenStream {
             )*
         }
 
-        #[automatically_derived]
-        impl #setters_ident<#db> for #entity_ident {
-            #(
-                #setters
-            )*
-        }
+        #setters_impls
     }
 }
 
@@ -327,7 +345,7 @@ fn expand_setter(
             &'e mut self,
             manager: M,
             #field_name: #input_ty,
-        ) -> ::foil::manager::BoxFuture<'o, ::std::result::Result<(), M::Error>>
+        ) -> ::foil::manager::BoxFuture<'o, ::std::result::Result<(), ::foil::entity::ModifyOneError<M::Error>>>
         #impl_
     }
 }
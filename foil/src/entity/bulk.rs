@@ -0,0 +1,122 @@
+use crate::entity::{Create, Entity};
+use crate::manager::{Dialect, PostgresDialect};
+use futures::{future::BoxFuture, Stream, StreamExt};
+use sqlx::{
+    encode::IsNull,
+    postgres::{PgArgumentBuffer, PgConnection},
+    Encode, Postgres, Type, TypeInfo,
+};
+use thiserror::Error;
+
+const COPY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const COPY_FLUSH_SIZE: usize = 64 * 1024;
+
+pub trait BulkCreate: Create<Postgres> {
+    fn copy_col_names() -> &'static [&'static str];
+
+    fn write_copy_row(input: &Self::Input<'_>, row: &mut CopyRow) -> Result<(), CopyError>;
+
+    fn copy_in<'c: 'o, 'q: 'o, 'o, S>(
+        conn: &'c mut PgConnection,
+        inputs: S,
+    ) -> BoxFuture<'o, Result<u64, CopyError>>
+    where
+        S: Stream<Item = Self::Input<'q>> + Send + 'o,
+    {
+        Box::pin(async move {
+            let statement = copy_statement(
+                <Self as Entity<Postgres>>::table_name(),
+                Self::copy_col_names(),
+            )?;
+
+            let field_count =
+                i16::try_from(Self::copy_col_names().len()).map_err(|_| CopyError::TooManyCols)?;
+
+            let mut copy = conn.copy_in_raw(&statement).await?;
+
+            let mut buf = Vec::with_capacity(COPY_FLUSH_SIZE);
+            buf.extend_from_slice(COPY_SIGNATURE);
+            buf.extend_from_slice(&0_i32.to_be_bytes());
+            buf.extend_from_slice(&0_i32.to_be_bytes());
+
+            let mut inputs = Box::pin(inputs);
+            while let Some(input) = inputs.next().await {
+                buf.extend_from_slice(&field_count.to_be_bytes());
+
+                if let Err(err) = Self::write_copy_row(&input, &mut CopyRow(&mut buf)) {
+                    copy.abort(err.to_string()).await?;
+                    return Err(err);
+                }
+
+                if buf.len() >= COPY_FLUSH_SIZE {
+                    copy.send(std::mem::take(&mut buf)).await?;
+                }
+            }
+
+            buf.extend_from_slice(&(-1_i16).to_be_bytes());
+            copy.send(buf).await?;
+
+            Ok(copy.finish().await?)
+        })
+    }
+}
+
+fn copy_statement(table_name: &str, col_names: &[&str]) -> Result<String, std::fmt::Error> {
+    let mut statement = "COPY ".to_owned();
+    PostgresDialect.write_ident(&mut statement, table_name)?;
+    statement.push_str(" (");
+
+    for (index, col_name) in col_names.iter().enumerate() {
+        if index > 0 {
+            statement.push_str(", ");
+        }
+        PostgresDialect.write_ident(&mut statement, col_name)?;
+    }
+
+    statement.push_str(") FROM STDIN (FORMAT binary)");
+    Ok(statement)
+}
+
+pub struct CopyRow<'b>(pub(crate) &'b mut Vec<u8>);
+
+impl CopyRow<'_> {
+    pub fn field<'e, T: Encode<'e, Postgres> + Type<Postgres>>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), CopyError> {
+        let type_info = value.produces().unwrap_or_else(T::type_info);
+
+        if type_info.oid().is_none() && type_info.name().starts_with('_') {
+            return Err(CopyError::UnresolvedType(type_info.name().to_owned()));
+        }
+
+        let mut field = PgArgumentBuffer::default();
+
+        match value.encode_by_ref(&mut field) {
+            IsNull::Yes => self.0.extend_from_slice(&(-1_i32).to_be_bytes()),
+            IsNull::No => {
+                let len = i32::try_from(field.len()).map_err(|_| CopyError::FieldTooLarge)?;
+                self.0.extend_from_slice(&len.to_be_bytes());
+                self.0.extend_from_slice(&field);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CopyError {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+    #[error("generated column {0} can't be set when copying")]
+    GeneratedColSet(&'static str),
+    #[error("too many columns to copy")]
+    TooManyCols,
+    #[error("field is too large to copy")]
+    FieldTooLarge,
+    #[error("type {0} must be resolved by the database and can't be copied")]
+    UnresolvedType(String),
+}
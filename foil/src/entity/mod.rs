@@ -1,16 +1,28 @@
 use crate::manager::{
-    CountQuery, DeleteQuery, FindOperator, FromRecord, InsertQuery, InsertReturningQuery,
-    IntoSelector, Manager, OrderBy, Record, RecordError, SelectQuery, Selector, ToInputRecord,
-    UpdateQuery, Value,
+    Aggregate, AggregateQuery, Begin, CountQuery, DeleteQuery, DeleteReturningQuery, Exists,
+    Filter, FindOperator, FromRecord, InputRecord, InsertQuery, InsertReturningQuery, IntoSelector,
+    Manager, OnConflict, Order, OrderBy, Record, RecordError, RowOperator, SelectQuery, Selector,
+    Subquery, ToInputRecord, Transaction, UnnestInsertQuery, UpdateQuery, UpdateReturningQuery,
+    UpsertQuery, Value,
 };
 use futures::{
-    future::BoxFuture, stream::BoxStream, FutureExt, StreamExt, TryFutureExt, TryStreamExt,
+    future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt,
 };
+use itertools::Itertools;
 use sqlx::Database;
+use std::any::Any;
+use std::collections::HashSet;
 use std::error::Error;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use thiserror::Error;
 
+#[cfg(feature = "postgres")]
+mod bulk;
+
+#[cfg(feature = "postgres")]
+pub use bulk::{BulkCreate, CopyError, CopyRow};
+
 #[cfg(all(
     test,
     feature = "test-manager",
@@ -23,7 +35,7 @@ mod test;
 pub trait Entity<DB: Database>: FromRecord<DB> + 'static {
     type Col: Col + Send;
     type Id: for<'q> Value<'q, DB> + Send + Clone;
-    type Selector<'q>: IntoSelector<'q, DB> + Default + Send;
+    type Selector<'q>: IntoSelector<'q, DB> + Clone + Default + Send;
 
     fn table_name() -> &'static str;
 
@@ -43,8 +55,8 @@ pub trait Entity<DB: Database>: FromRecord<DB> + 'static {
         Box::pin(
             Selection::<_, Self, DB>::new(manager.select(SelectQuery {
                 table_name: Self::table_name(),
-                col_names: Self::col_names(),
-                selectors: vec![selector],
+                col_names: Self::col_names().into(),
+                filter: selector.into(),
                 order_by: None,
                 offset: None,
                 limit: None,
@@ -57,15 +69,7 @@ pub trait Entity<DB: Database>: FromRecord<DB> + 'static {
         manager: M,
         selectors: Vec<Self::Selector<'q>>,
     ) -> Selection<'o, M::Error, Self, DB> {
-        Self::find_with_options(
-            manager,
-            selectors,
-            FindOptions {
-                order_by: None,
-                offset: None,
-                limit: None,
-            },
-        )
+        Self::find_with_options(manager, selectors, FindOptions::default())
     }
 
     fn find_with_options<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
@@ -73,41 +77,196 @@ pub trait Entity<DB: Database>: FromRecord<DB> + 'static {
         selectors: Vec<Self::Selector<'q>>,
         options: FindOptions<Self::Col>,
     ) -> Selection<'o, M::Error, Self, DB> {
+        Self::find_where(manager, Filter::from_selectors(selectors), options)
+    }
+
+    fn find_where<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        filter: Filter<'q, DB>,
+        options: FindOptions<Self::Col>,
+    ) -> Selection<'o, M::Error, Self, DB> {
+        let col_names = if options.include.is_empty() {
+            Self::col_names().into()
+        } else {
+            Self::col_names()
+                .iter()
+                .copied()
+                .chain(options.include.iter().map(Col::as_str))
+                .unique()
+                .collect::<Vec<_>>()
+                .into()
+        };
+
         Selection::new(
             manager.select(SelectQuery {
                 table_name: Self::table_name(),
-                col_names: Self::col_names(),
-                selectors: selectors
-                    .into_iter()
-                    .map(IntoSelector::into_selector)
-                    .collect(),
-                order_by: options.order_by.map(|order_by| OrderBy {
-                    order: order_by.order,
-                    cols: order_by.cols.iter().map(Col::as_str).collect(),
-                }),
+                col_names,
+                filter,
+                order_by: options
+                    .order_by
+                    .map(|order_by| order_by.map_cols(|col| col.as_str())),
                 offset: options.offset,
                 limit: options.limit,
             }),
         )
     }
 
+    fn project<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>, R: FromRecord<DB>>(
+        manager: M,
+        selectors: Vec<Self::Selector<'q>>,
+        cols: Vec<Self::Col>,
+        options: FindOptions<Self::Col>,
+    ) -> Selection<'o, M::Error, R, DB> {
+        Self::project_where(manager, Filter::from_selectors(selectors), cols, options)
+    }
+
+    fn project_where<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>, R: FromRecord<DB>>(
+        manager: M,
+        filter: Filter<'q, DB>,
+        cols: Vec<Self::Col>,
+        options: FindOptions<Self::Col>,
+    ) -> Selection<'o, M::Error, R, DB> {
+        Selection::new(
+            manager.select(SelectQuery {
+                table_name: Self::table_name(),
+                col_names: cols
+                    .iter()
+                    .chain(options.include.iter())
+                    .map(Col::as_str)
+                    .unique()
+                    .collect::<Vec<_>>()
+                    .into(),
+                filter,
+                order_by: options
+                    .order_by
+                    .map(|order_by| order_by.map_cols(|col| col.as_str())),
+                offset: options.offset,
+                limit: options.limit,
+            }),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn find_by_keys<'m: 'o, 'o, M: Manager<'m, DB>, K>(
+        manager: M,
+        col_name: &'static str,
+        keys: Vec<K>,
+    ) -> BoxFuture<'o, Result<Vec<(K, Self)>, SelectError<M::Error>>>
+    where
+        K: for<'q> Value<'q, DB>
+            + sqlx::Type<DB>
+            + for<'d> sqlx::Decode<'d, DB>
+            + Clone
+            + Eq
+            + Hash
+            + Any,
+        for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    {
+        let records = manager.select(SelectQuery {
+            table_name: Self::table_name(),
+            col_names: Self::col_names().into(),
+            filter: Filter::condition(
+                col_name,
+                FindOperator::In(keys.into_iter().unique().collect()),
+            ),
+            order_by: None,
+            offset: None,
+            limit: None,
+        });
+
+        Box::pin(async move {
+            let records = records
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(SelectError::Manager)?;
+
+            records
+                .iter()
+                .map(|record| Ok((record.col(col_name)?, Self::from_record(record)?)))
+                .collect()
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn load_lazy<'m: 'o, 'o, M: Manager<'m, DB>, T>(
+        manager: M,
+        col_name: &'static str,
+        ids: Vec<Self::Id>,
+    ) -> BoxFuture<'o, Result<Vec<(Self::Id, T)>, SelectError<M::Error>>>
+    where
+        Self::Id: sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Eq + Hash + Any,
+        T: sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Clone + Any,
+        for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    {
+        let records = manager.select(SelectQuery {
+            table_name: Self::table_name(),
+            col_names: vec![Self::id_col_name(), col_name].into(),
+            filter: Filter::condition(
+                Self::id_col_name(),
+                FindOperator::In(ids.into_iter().unique().collect()),
+            ),
+            order_by: None,
+            offset: None,
+            limit: None,
+        });
+
+        Box::pin(async move {
+            let records = records
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(SelectError::Manager)?;
+
+            records
+                .iter()
+                .map(|record| Ok((record.col(Self::id_col_name())?, record.col(col_name)?)))
+                .collect()
+        })
+    }
+
     fn count<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
         manager: M,
         selectors: Vec<Self::Selector<'q>>,
     ) -> BoxFuture<'o, Result<i64, M::Error>>
+    where
+        for<'a> i64: sqlx::Type<DB> + sqlx::Decode<'a, DB>,
+        for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    {
+        Self::count_where(manager, Filter::from_selectors(selectors))
+    }
+
+    fn count_where<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        filter: Filter<'q, DB>,
+    ) -> BoxFuture<'o, Result<i64, M::Error>>
     where
         for<'a> i64: sqlx::Type<DB> + sqlx::Decode<'a, DB>,
         for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
     {
         manager.count(CountQuery {
             table_name: Self::table_name(),
-            selectors: selectors
-                .into_iter()
-                .map(IntoSelector::into_selector)
-                .collect(),
+            filter,
         })
     }
 
+    fn aggregate<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>, R: FromRecord<DB>>(
+        manager: M,
+        selectors: Vec<Self::Selector<'q>>,
+        aggregates: Vec<Aggregate<Self::Col>>,
+        group_by: Vec<Self::Col>,
+    ) -> Selection<'o, M::Error, R, DB> {
+        Selection::new(
+            manager.aggregate(AggregateQuery {
+                table_name: Self::table_name(),
+                aggregates: aggregates
+                    .into_iter()
+                    .map(|aggregate| aggregate.map_col(|col| col.as_str()))
+                    .collect(),
+                group_by: group_by.iter().map(Col::as_str).collect(),
+                filter: Filter::from_selectors(selectors),
+            }),
+        )
+    }
+
     fn exists<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
         manager: M,
         selectors: Vec<Self::Selector<'q>>,
@@ -118,12 +277,437 @@ pub trait Entity<DB: Database>: FromRecord<DB> + 'static {
     {
         Box::pin(Self::count(manager, selectors).map_ok(|count| count != 0))
     }
+
+    #[allow(clippy::type_complexity)]
+    fn find_page_after<'q: 'o, 'o, M, E, K: CursorKey<DB> + Send + 'o>(
+        manager: &'o mut M,
+        selectors: Vec<Self::Selector<'q>>,
+        order_by: OrderBy<Self::Col>,
+        cursor: Option<Cursor<K>>,
+        limit: u32,
+    ) -> BoxFuture<'o, Result<Page<Self, K>, SelectError<E>>>
+    where
+        M: Send,
+        for<'b> &'b mut M: Manager<'b, DB, Error = E>,
+        E: Error + Send + Sync + 'static,
+        for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    {
+        Box::pin(async move {
+            let order_by = order_by.map_cols(|col| col.as_str());
+            if order_by.cols.iter().any(|col| col.nulls.is_some())
+                || cursor
+                    .as_ref()
+                    .is_some_and(|cursor| cursor.key.values().iter().any(|value| value.is_null()))
+            {
+                return Err(SelectError::NullKey);
+            }
+
+            let backward = matches!(
+                cursor,
+                Some(Cursor {
+                    direction: CursorDirection::Before,
+                    ..
+                })
+            );
+
+            let with_keyset = |filter: Filter<'q, DB>, keyset: Filter<'q, DB>| {
+                if filter.is_all() {
+                    keyset
+                } else {
+                    Filter::And(vec![filter, keyset])
+                }
+            };
+
+            let col_names = order_by.cols.iter().map(|col| col.col).collect::<Vec<_>>();
+            let has_before_cursor = match cursor.as_ref() {
+                Some(cursor) => {
+                    let probe = (&mut *manager)
+                        .select(SelectQuery {
+                            table_name: Self::table_name(),
+                            col_names: col_names.clone().into(),
+                            filter: with_keyset(
+                                Filter::from_selectors(selectors.clone()),
+                                Filter::Not(Box::new(keyset_filter(
+                                    &order_by,
+                                    &cursor.key,
+                                    !backward,
+                                ))),
+                            ),
+                            order_by: None,
+                            offset: None,
+                            limit: Some(1),
+                        })
+                        .try_collect::<Vec<_>>()
+                        .await
+                        .map_err(SelectError::Manager)?;
+                    !probe.is_empty()
+                }
+                None => false,
+            };
+
+            let mut filter = Filter::from_selectors(selectors);
+            if let Some(cursor) = cursor.as_ref() {
+                filter = with_keyset(filter, keyset_filter(&order_by, &cursor.key, !backward));
+            }
+
+            let order_by = if backward {
+                order_by.reverse()
+            } else {
+                order_by
+            };
+
+            let mut records = (&mut *manager)
+                .select(SelectQuery {
+                    table_name: Self::table_name(),
+                    col_names: Self::col_names().into(),
+                    filter,
+                    order_by: Some(order_by),
+                    offset: None,
+                    limit: Some(limit.saturating_add(1)),
+                })
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(SelectError::Manager)?;
+
+            let has_more = records.len() > limit as usize;
+            records.truncate(limit as usize);
+            if backward {
+                records.reverse();
+            }
+
+            let (first, last) = match (records.first(), records.last()) {
+                (Some(first), Some(last)) => (
+                    Some(K::from_record(first, &col_names)?),
+                    Some(K::from_record(last, &col_names)?),
+                ),
+                _ => (None, None),
+            };
+
+            let (has_next, has_previous) = if backward {
+                (has_before_cursor, has_more)
+            } else {
+                (has_more, has_before_cursor)
+            };
+
+            Ok(Page {
+                items: records
+                    .iter()
+                    .map(Self::from_record)
+                    .collect::<Result<_, _>>()?,
+                next: last.filter(|_| has_next).map(Cursor::after),
+                previous: first.filter(|_| has_previous).map(Cursor::before),
+            })
+        })
+    }
 }
 
 pub struct FindOptions<C> {
     pub order_by: Option<OrderBy<C>>,
     pub offset: Option<u32>,
     pub limit: Option<u32>,
+    pub include: Vec<C>,
+}
+
+impl<C> Default for FindOptions<C> {
+    fn default() -> Self {
+        Self {
+            order_by: None,
+            offset: None,
+            limit: None,
+            include: Vec::new(),
+        }
+    }
+}
+
+pub struct Page<T, K> {
+    pub items: Vec<T>,
+    pub next: Option<Cursor<K>>,
+    pub previous: Option<Cursor<K>>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cursor<K> {
+    key: K,
+    direction: CursorDirection,
+}
+
+impl<K> Cursor<K> {
+    fn after(key: K) -> Self {
+        Self {
+            key,
+            direction: CursorDirection::After,
+        }
+    }
+
+    fn before(key: K) -> Self {
+        Self {
+            key,
+            direction: CursorDirection::Before,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum CursorDirection {
+    After,
+    Before,
+}
+
+pub trait CursorKey<DB: Database>: Sized {
+    fn from_record(record: &Record<DB>, col_names: &[&str]) -> Result<Self, RecordError>;
+
+    fn values<'q>(&self) -> Vec<Box<dyn Value<'q, DB> + 'q>>;
+}
+
+macro_rules! impl_cursor_key {
+    ( $( $type:ident $index:tt ),+ ) => {
+        impl<DB: Database, $( $type ),+> CursorKey<DB> for ( $( $type, )+ )
+        where
+            $( $type: for<'q> Value<'q, DB> + sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Clone + Any, )+
+            for<'a> &'a str: sqlx::ColumnIndex<DB::Row>,
+        {
+            fn from_record(record: &Record<DB>, col_names: &[&str]) -> Result<Self, RecordError> {
+                let len = [$( $index ),+].len();
+                if let Some(col_name) = col_names.get(len) {
+                    return Err(RecordError::ColumnNotFound((*col_name).into()));
+                }
+
+                Ok(( $(
+                    record.col::<$type>(
+                        col_names
+                            .get($index)
+                            .ok_or_else(|| RecordError::ColumnNotFound(format!("#{}", $index)))?,
+                    )?,
+                )+ ))
+            }
+
+            fn values<'q>(&self) -> Vec<Box<dyn Value<'q, DB> + 'q>> {
+                vec![$( Box::new(self.$index.clone()) ),+]
+            }
+        }
+    };
+}
+
+impl_cursor_key!(A 0);
+impl_cursor_key!(A 0, B 1);
+impl_cursor_key!(A 0, B 1, C 2);
+impl_cursor_key!(A 0, B 1, C 2, D 3);
+
+fn keyset_filter<'q, DB: Database, K: CursorKey<DB>>(
+    order_by: &OrderBy<&'q str>,
+    key: &K,
+    after: bool,
+) -> Filter<'q, DB> {
+    let operators = order_by
+        .cols
+        .iter()
+        .map(|col| match (col.order, after) {
+            (Order::Asc, true) | (Order::Desc, false) => RowOperator::Gt,
+            (Order::Asc, false) | (Order::Desc, true) => RowOperator::Lt,
+        })
+        .collect::<Vec<_>>();
+    let col_names = order_by.cols.iter().map(|col| col.col).collect::<Vec<_>>();
+
+    if operators.iter().all_equal() {
+        if let Some(operator) = operators.first() {
+            return Filter::Row(col_names, *operator, key.values());
+        }
+    }
+
+    Filter::Or(
+        (0..col_names.len())
+            .map(|index| {
+                Filter::And(
+                    key.values()
+                        .into_iter()
+                        .zip(col_names.iter().zip(operators.iter()))
+                        .take(index + 1)
+                        .enumerate()
+                        .map(|(position, (value, (col_name, operator)))| {
+                            let operator = if position < index {
+                                FindOperator::Eq(value)
+                            } else if *operator == RowOperator::Gt {
+                                FindOperator::Gt(value)
+                            } else {
+                                FindOperator::Lt(value)
+                            };
+                            Filter::Condition(col_name, operator)
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[must_use]
+pub fn exists<'q, DB: Database, P: Entity<DB>, C: Entity<DB>>(
+    outer_col_name: &'static str,
+    col_name: &'static str,
+    selectors: Vec<C::Selector<'q>>,
+) -> Filter<'q, DB> {
+    Filter::Exists(Box::new(Exists {
+        table_name: C::table_name(),
+        col_name,
+        outer_table_name: P::table_name(),
+        outer_col_name,
+        filter: Filter::from_selectors(selectors),
+    }))
+}
+
+#[derive(Clone, Copy)]
+pub struct JoinTable {
+    pub table_name: &'static str,
+    pub col_name: &'static str,
+    pub target_col_name: &'static str,
+}
+
+impl JoinTable {
+    pub fn find<'m: 'o, 'o, M: Manager<'m, DB>, DB: Database, S: Entity<DB>, T: Entity<DB>>(
+        &self,
+        manager: M,
+        source: &S,
+    ) -> Selection<'o, M::Error, T, DB> {
+        T::find_where(
+            manager,
+            Filter::InSubquery(
+                T::id_col_name(),
+                Box::new(Subquery {
+                    table_name: self.table_name,
+                    col_name: self.target_col_name,
+                    filter: Filter::condition(self.col_name, FindOperator::Eq(source.id())),
+                }),
+            ),
+            FindOptions::default(),
+        )
+    }
+
+    pub fn attach<'m: 'o, 'o, M: Manager<'m, DB>, DB: Database, S: Entity<DB>, T: Entity<DB>>(
+        &self,
+        manager: M,
+        source: &S,
+        ids: Vec<T::Id>,
+    ) -> BoxFuture<'o, Result<(), M::Error>> {
+        manager.insert(InsertQuery {
+            table_name: self.table_name,
+            values: self.input_records::<DB, S, T>(&source.id(), ids),
+        })
+    }
+
+    pub fn detach<'m: 'o, 'o, M: Manager<'m, DB>, DB: Database, S: Entity<DB>, T: Entity<DB>>(
+        &self,
+        manager: M,
+        source: &S,
+        ids: Vec<T::Id>,
+    ) -> BoxFuture<'o, Result<u64, M::Error>> {
+        manager.delete(DeleteQuery {
+            table_name: self.table_name,
+            filter: Filter::And(vec![
+                Filter::condition(self.col_name, FindOperator::Eq(source.id())),
+                Filter::condition(self.target_col_name, FindOperator::In(ids)),
+            ]),
+        })
+    }
+
+    pub fn sync<'t, 'o, X: Transaction<'t, DB>, DB: Database, S: Entity<DB>, T: Entity<DB>>(
+        &self,
+        transaction: &'o mut X,
+        source: &S,
+        ids: Vec<T::Id>,
+    ) -> BoxFuture<'o, Result<(), SyncError<X::Error>>>
+    where
+        T::Id: sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Eq + Hash + Any,
+        for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    {
+        let ids = ids.into_iter().unique().collect::<Vec<_>>();
+        let source_id = source.id();
+
+        let delete_query = DeleteQuery {
+            table_name: self.table_name,
+            filter: Filter::And(vec![
+                Filter::condition(self.col_name, FindOperator::Eq(source_id.clone())),
+                Filter::condition(self.target_col_name, FindOperator::NotIn(ids.clone())),
+            ]),
+        };
+
+        let select_query = SelectQuery {
+            table_name: self.table_name,
+            col_names: vec![self.target_col_name].into(),
+            filter: Filter::condition(self.col_name, FindOperator::Eq(source_id.clone())),
+            order_by: None,
+            offset: None,
+            limit: None,
+        };
+
+        let join_table = *self;
+
+        Box::pin(async move {
+            let mut nested = transaction.begin().await.map_err(SyncError::Manager)?;
+
+            let savepoint = &mut nested;
+            let result = async move {
+                savepoint
+                    .manager()
+                    .delete(delete_query)
+                    .await
+                    .map_err(SyncError::Manager)?;
+
+                let existing = savepoint
+                    .manager()
+                    .select(select_query)
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .map_err(SyncError::Manager)?
+                    .iter()
+                    .map(|record| record.col::<T::Id>(join_table.target_col_name))
+                    .collect::<Result<HashSet<_>, _>>()?;
+
+                let ids = ids
+                    .into_iter()
+                    .filter(|id| !existing.contains(id))
+                    .collect::<Vec<_>>();
+
+                if !ids.is_empty() {
+                    savepoint
+                        .manager()
+                        .insert(InsertQuery {
+                            table_name: join_table.table_name,
+                            values: join_table.input_records::<DB, S, T>(&source_id, ids),
+                        })
+                        .await
+                        .map_err(SyncError::Manager)?;
+                }
+
+                Ok(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => nested.commit().await.map_err(SyncError::Manager),
+                Err(err) => {
+                    let _ = nested.rollback().await;
+                    Err(err)
+                }
+            }
+        })
+    }
+
+    fn input_records<DB: Database, S: Entity<DB>, T: Entity<DB>>(
+        &self,
+        source_id: &S::Id,
+        ids: Vec<T::Id>,
+    ) -> Vec<InputRecord<'static, DB>> {
+        ids.into_iter()
+            .map(|id| {
+                let mut input_record = InputRecord::new();
+                input_record.add_col(self.col_name, Box::new(source_id.clone()));
+                input_record.add_col(self.target_col_name, Box::new(id));
+                input_record
+            })
+            .collect()
+    }
 }
 
 pub trait Create<DB: Database>: Entity<DB> + Send {
@@ -131,7 +715,11 @@ pub trait Create<DB: Database>: Entity<DB> + Send {
 
     fn generated_col_names() -> &'static [&'static str];
 
-    fn construct<'q>(input: &Self::Input<'q>, generated: &Record<DB>) -> Result<Self, RecordError>;
+    fn construct(input: &Self::Input<'_>, generated: &Record<DB>) -> Result<Self, RecordError>;
+
+    fn unnest_columns<'q>(_inputs: &[Self::Input<'q>]) -> Option<InputRecord<'q, DB>> {
+        None
+    }
 
     fn create<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
         manager: M,
@@ -144,33 +732,42 @@ pub trait Create<DB: Database>: Entity<DB> + Send {
         manager: M,
         inputs: Vec<Self::Input<'q>>,
     ) -> BoxFuture<'o, Result<Vec<Self>, CreateError<M::Error>>> {
-        Box::pin(
-            manager
-                .insert_returning(InsertReturningQuery {
-                    insert_query: InsertQuery {
-                        table_name: Self::table_name(),
-                        values: inputs
-                            .iter()
-                            .map(ToInputRecord::to_input_record)
-                            .collect::<Vec<_>>(),
-                    },
-                    returning_cols: Self::generated_col_names(),
-                })
-                .try_collect::<Vec<_>>()
-                .map(move |result| match result {
-                    Ok(records) => Ok(records
-                        .iter()
-                        .enumerate()
-                        .map(|(index, record)| {
-                            Ok::<_, CreateError<_>>(Self::construct(
-                                inputs.get(index).ok_or(CreateError::WrongNumberOfRows)?,
-                                record,
-                            )?)
-                        })
-                        .collect::<Result<Vec<_>, _>>()?),
-                    Err(err) => Err(CreateError::Manager(err)),
-                }),
-        )
+        let insert_returning_query = InsertReturningQuery {
+            insert_query: InsertQuery {
+                table_name: Self::table_name(),
+                values: inputs
+                    .iter()
+                    .map(ToInputRecord::to_input_record)
+                    .collect::<Vec<_>>(),
+            },
+            returning_cols: Self::generated_col_names(),
+            generated_key: Some(Self::id_col_name()),
+        };
+
+        let records = if let Some(columns) = Self::unnest_columns(&inputs) {
+            manager.insert_unnest(UnnestInsertQuery {
+                insert_returning_query,
+                columns,
+            })
+        } else {
+            manager.insert_returning(insert_returning_query)
+        };
+
+        Box::pin(records.try_collect::<Vec<_>>().map(move |result| {
+            match result {
+                Ok(records) => Ok(records
+                    .iter()
+                    .enumerate()
+                    .map(|(index, record)| {
+                        Ok::<_, CreateError<_>>(Self::construct(
+                            inputs.get(index).ok_or(CreateError::WrongNumberOfRows)?,
+                            record,
+                        )?)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?),
+                Err(err) => Err(CreateError::Manager(err)),
+            }
+        }))
     }
 
     fn persist<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
@@ -184,9 +781,94 @@ pub trait Create<DB: Database>: Entity<DB> + Send {
         manager: M,
         inputs: Vec<Self::Input<'q>>,
     ) -> BoxFuture<'o, Result<(), M::Error>> {
-        manager.insert(InsertQuery {
+        let insert_query = InsertQuery {
             table_name: Self::table_name(),
             values: inputs.iter().map(ToInputRecord::to_input_record).collect(),
+        };
+
+        if let Some(columns) = Self::unnest_columns(&inputs) {
+            return Box::pin(
+                manager
+                    .insert_unnest(UnnestInsertQuery {
+                        insert_returning_query: InsertReturningQuery {
+                            insert_query,
+                            returning_cols: &[],
+                            generated_key: None,
+                        },
+                        columns,
+                    })
+                    .try_collect::<Vec<_>>()
+                    .map_ok(|_| ()),
+            );
+        }
+
+        manager.insert(insert_query)
+    }
+
+    fn insert_stream<'q: 'o, 'o, M, E, S>(
+        manager: &'o mut M,
+        inputs: S,
+        batch_size: usize,
+    ) -> BoxStream<'o, Result<InsertProgress, InsertBatchError<E>>>
+    where
+        M: Send,
+        for<'b> &'b mut M: Begin<'b, DB, Error = E>,
+        E: Error + Send + Sync + 'static,
+        S: Stream<Item = Self::Input<'q>> + Send + 'o,
+    {
+        Box::pin(async_stream::stream! {
+            let mut batches = Box::pin(inputs.chunks(batch_size.max(1)));
+            let mut batch = 0;
+            let mut total_rows = 0;
+
+            while let Some(inputs) = batches.next().await {
+                let rows = inputs.len();
+
+                let result = async {
+                    let mut transaction = (&mut *manager).begin().await?;
+
+                    match Self::insert(transaction.manager(), inputs).await {
+                        Ok(()) => transaction.commit().await,
+                        Err(err) => {
+                            let _ = transaction.rollback().await;
+                            Err(err)
+                        }
+                    }
+                }
+                .await;
+
+                match result {
+                    Ok(()) => {
+                        total_rows += rows;
+                        yield Ok(InsertProgress { batch, rows, total_rows });
+                    }
+                    Err(source) => yield Err(InsertBatchError { batch, rows, source }),
+                }
+
+                batch += 1;
+            }
+        })
+    }
+
+    fn upsert<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        input: Self::Input<'q>,
+        on_conflict: OnConflict<'q, Self::Col>,
+    ) -> BoxFuture<'o, Result<u64, M::Error>> {
+        Self::upsert_many(manager, vec![input], on_conflict)
+    }
+
+    fn upsert_many<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        inputs: Vec<Self::Input<'q>>,
+        on_conflict: OnConflict<'q, Self::Col>,
+    ) -> BoxFuture<'o, Result<u64, M::Error>> {
+        manager.upsert(UpsertQuery {
+            insert_query: InsertQuery {
+                table_name: Self::table_name(),
+                values: inputs.iter().map(ToInputRecord::to_input_record).collect(),
+            },
+            on_conflict: on_conflict.map_cols(|col| col.as_str()),
         })
     }
 }
@@ -201,6 +883,21 @@ pub enum CreateError<E: Error + Send + Sync> {
     WrongNumberOfRows,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InsertProgress {
+    pub batch: usize,
+    pub rows: usize,
+    pub total_rows: usize,
+}
+
+#[derive(Debug, Error)]
+#[error("failed to insert batch {batch} of {rows} rows")]
+pub struct InsertBatchError<E: Error + Send + Sync + 'static> {
+    pub batch: usize,
+    pub rows: usize,
+    pub source: E,
+}
+
 pub trait Update<DB: Database>: Entity<DB> + Send {
     type Patch<'q>: ToInputRecord<'q, DB> + Default + Send + Sync;
 
@@ -210,7 +907,13 @@ pub trait Update<DB: Database>: Entity<DB> + Send {
         &'e mut self,
         manager: M,
         patch: Self::Patch<'q>,
-    ) -> BoxFuture<'o, Result<(), M::Error>> {
+    ) -> BoxFuture<'o, Result<(), ModifyOneError<M::Error>>> {
+        let new_values = patch.to_input_record();
+
+        if new_values.is_empty() {
+            return Box::pin(async { Ok(()) });
+        }
+
         let mut selector = Selector::new();
         selector.add_col(
             Self::id_col_name(),
@@ -221,10 +924,15 @@ pub trait Update<DB: Database>: Entity<DB> + Send {
             manager
                 .update(UpdateQuery {
                     table_name: Self::table_name(),
-                    selectors: vec![selector],
-                    new_values: patch.to_input_record(),
+                    filter: selector.into(),
+                    new_values,
                 })
-                .and_then(|_| async {
+                .map_err(ModifyOneError::Manager)
+                .and_then(|rows_affected| async move {
+                    if rows_affected == 0 {
+                        return Err(ModifyOneError::RowNotFound);
+                    }
+
                     self.apply_patch(patch);
                     Ok(())
                 }),
@@ -235,46 +943,88 @@ pub trait Update<DB: Database>: Entity<DB> + Send {
         manager: M,
         selectors: Vec<Self::Selector<'q>>,
         patch: Self::Patch<'q>,
-    ) -> BoxFuture<'o, Result<(), M::Error>> {
+    ) -> BoxFuture<'o, Result<u64, M::Error>> {
         manager.update(UpdateQuery {
             table_name: Self::table_name(),
-            selectors: selectors
-                .into_iter()
-                .map(IntoSelector::into_selector)
-                .collect(),
+            filter: Filter::from_selectors(selectors),
             new_values: patch.to_input_record(),
         })
     }
+
+    fn update_returning<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        selectors: Vec<Self::Selector<'q>>,
+        patch: Self::Patch<'q>,
+    ) -> Selection<'o, M::Error, Self, DB> {
+        Selection::new(manager.update_returning(UpdateReturningQuery {
+            update_query: UpdateQuery {
+                table_name: Self::table_name(),
+                filter: Filter::from_selectors(selectors),
+                new_values: patch.to_input_record(),
+            },
+            returning_cols: Self::col_names(),
+        }))
+    }
 }
 
 pub trait Delete<DB: Database>: Entity<DB> {
-    fn remove<'m, M: Manager<'m, DB>>(&self, manager: M) -> BoxFuture<'m, Result<(), M::Error>> {
+    fn remove<'m, M: Manager<'m, DB>>(
+        &self,
+        manager: M,
+    ) -> BoxFuture<'m, Result<(), ModifyOneError<M::Error>>> {
         let mut selector = Selector::new();
         selector.add_col(Self::id_col_name(), FindOperator::Eq(Box::new(self.id())));
-        manager.delete(DeleteQuery {
-            table_name: Self::table_name(),
-            selectors: vec![selector],
-        })
+        Box::pin(
+            manager
+                .delete(DeleteQuery {
+                    table_name: Self::table_name(),
+                    filter: selector.into(),
+                })
+                .map(|result| match result {
+                    Ok(0) => Err(ModifyOneError::RowNotFound),
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(ModifyOneError::Manager(err)),
+                }),
+        )
     }
 
     fn delete<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
         manager: M,
         selectors: Vec<Self::Selector<'q>>,
-    ) -> BoxFuture<'o, Result<(), M::Error>> {
+    ) -> BoxFuture<'o, Result<u64, M::Error>> {
         manager.delete(DeleteQuery {
             table_name: Self::table_name(),
-            selectors: selectors
-                .into_iter()
-                .map(IntoSelector::into_selector)
-                .collect(),
+            filter: Filter::from_selectors(selectors),
         })
     }
+
+    fn delete_returning<'m: 'o, 'q: 'o, 'o, M: Manager<'m, DB>>(
+        manager: M,
+        selectors: Vec<Self::Selector<'q>>,
+    ) -> Selection<'o, M::Error, Self, DB> {
+        Selection::new(manager.delete_returning(DeleteReturningQuery {
+            delete_query: DeleteQuery {
+                table_name: Self::table_name(),
+                filter: Filter::from_selectors(selectors),
+            },
+            returning_cols: Self::col_names(),
+        }))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ModifyOneError<E: Error + Send + Sync> {
+    #[error(transparent)]
+    Manager(E),
+    #[error("query affected less rows than expected")]
+    RowNotFound,
 }
 
 pub trait Col: Copy {
     fn as_str(&self) -> &'static str;
 }
 
+#[derive(Clone)]
 pub enum Field<T> {
     Set(T),
     Omit,
@@ -297,6 +1047,8 @@ pub enum SelectError<E: Error + Send + Sync> {
     Manager(E),
     #[error(transparent)]
     Record(#[from] RecordError),
+    #[error("keyset pagination does not support NULL keys")]
+    NullKey,
 }
 
 #[derive(Debug, Error)]
@@ -307,6 +1059,14 @@ pub enum SelectOneError<E: Error + Send + Sync> {
     RowNotFound,
 }
 
+#[derive(Debug, Error)]
+pub enum SyncError<E: Error + Send + Sync> {
+    #[error(transparent)]
+    Manager(E),
+    #[error(transparent)]
+    Record(#[from] RecordError),
+}
+
 impl<'m, T: FromRecord<DB>, DB: Database, E: Error + Send + Sync + 'm> Selection<'m, E, T, DB> {
     fn new(stream: BoxStream<'m, Result<Record<DB>, E>>) -> Self {
         Self {
@@ -339,4 +1099,9 @@ impl<'m, T: FromRecord<DB>, DB: Database, E: Error + Send + Sync + 'm> Selection
     pub async fn all(self) -> Result<Vec<T>, SelectError<E>> {
         self.stream().try_collect().await
     }
+
+    #[must_use]
+    pub fn with_lazy<L: FromRecord<DB>>(self) -> Selection<'m, E, (T, L), DB> {
+        Selection::new(self.stream)
+    }
 }
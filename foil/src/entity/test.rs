@@ -1,9 +1,10 @@
 use super::{Col, Field};
 use crate::{
-    entity::{FindOptions, OrderBy},
+    entity::{Cursor, FindOptions, OrderBy, Page},
     manager::{
-        impls::mock::MockManager, FindOperator, FromRecord, InputRecord, IntoSelector, Order,
-        Record, RecordError, Selector, ToInputRecord, Value,
+        escape_like, impls::mock::MockManager, Aggregate, AggregateFunction, ConflictAction,
+        ConflictTarget, Filter, FindOperator, FromRecord, InputRecord, IntoSelector, Nulls,
+        OnConflict, Order, OrderByCol, Record, RecordError, Selector, ToInputRecord, Value,
     },
     Create, Delete, Entity, Update,
 };
@@ -64,13 +65,13 @@ impl Col for CharacterCol {
         match self {
             Self::Id => "id",
             Self::Name => "name",
-            Self::IsHandsome => "is_hansome",
+            Self::IsHandsome => "is_handsome",
             Self::FatherName => "father_name",
         }
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct CharacterSelector<'q> {
     id: Field<FindOperator<u8>>,
     name: Field<FindOperator<&'q str>>,
@@ -214,7 +215,7 @@ pub trait CharacterSetters<DB: ::sqlx::Database>: Update<DB> {
         &'e mut self,
         manager: M,
         name: &'q str,
-    ) -> crate::manager::BoxFuture<'o, Result<(), M::Error>>;
+    ) -> crate::manager::BoxFuture<'o, Result<(), crate::entity::ModifyOneError<M::Error>>>;
 }
 
 impl CharacterSetters<::sqlx::Sqlite> for Character {
@@ -222,7 +223,7 @@ impl CharacterSetters<::sqlx::Sqlite> for Character {
         &'e mut self,
         manager: M,
         name: &'q str,
-    ) -> crate::manager::BoxFuture<'o, Result<(), M::Error>> {
+    ) -> crate::manager::BoxFuture<'o, Result<(), crate::entity::ModifyOneError<M::Error>>> {
         self.patch(
             manager,
             CharacterPatch {
@@ -424,23 +425,23 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn eq_null() {
+        async fn lt() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Omit,
+                    id: Field::Set(FindOperator::Lt(1)),
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Set(FindOperator::Eq(None)),
+                    father_name: Field::Omit,
                 }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IS NULL"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" < ?"###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
@@ -454,71 +455,55 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn ne_null() {
+        async fn le() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Omit,
+                    id: Field::Set(FindOperator::Le(0)),
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Set(FindOperator::Ne(None)),
+                    father_name: Field::Omit,
                 }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IS NOT NULL"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" <= ?"###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
-                    id: 1,
-                    name: "Himmly",
-                    is_handsome: false,
-                    father_name: Some(
-                        "Gloyne",
-                    ),
-                },
-                Character {
-                    id: 2,
-                    name: "Aragorn",
+                    id: 0,
+                    name: "Legalas",
                     is_handsome: true,
-                    father_name: Some(
-                        "Arathorn",
-                    ),
+                    father_name: None,
                 },
             ]
             "###);
         }
 
         #[tokio::test]
-        async fn in_with_null() {
+        async fn gt() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Omit,
+                    id: Field::Set(FindOperator::Gt(1)),
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Set(FindOperator::In(vec![Some("Arathorn".into()), None])),
+                    father_name: Field::Omit,
                 }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IN (?) OR "father_name" IS NULL"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" > ?"###);
             assert_debug_snapshot!(characters, @r###"
             [
-                Character {
-                    id: 0,
-                    name: "Legalas",
-                    is_handsome: true,
-                    father_name: None,
-                },
                 Character {
                     id: 2,
                     name: "Aragorn",
@@ -532,34 +517,31 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn not_in_with_null() {
+        async fn ge() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Omit,
+                    id: Field::Set(FindOperator::Ge(2)),
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Set(FindOperator::NotIn(vec![
-                        Some("Arathorn".into()),
-                        None,
-                    ])),
+                    father_name: Field::Omit,
                 }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" NOT IN (?) AND "father_name" IS NOT NULL"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" >= ?"###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
-                    id: 1,
-                    name: "Himmly",
-                    is_handsome: false,
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
                     father_name: Some(
-                        "Gloyne",
+                        "Arathorn",
                     ),
                 },
             ]
@@ -567,13 +549,13 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn empty_cond() {
+        async fn between() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Omit,
+                    id: Field::Set(FindOperator::Between(1, 2)),
                     name: Field::Omit,
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
@@ -583,15 +565,9 @@ mod entity {
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character""###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" BETWEEN ? AND ?"###);
             assert_debug_snapshot!(characters, @r###"
             [
-                Character {
-                    id: 0,
-                    name: "Legalas",
-                    is_handsome: true,
-                    father_name: None,
-                },
                 Character {
                     id: 1,
                     name: "Himmly",
@@ -613,23 +589,23 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn multiple_fields() {
+        async fn like() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Set(FindOperator::Eq(true)),
-                    father_name: Field::Set(FindOperator::Ne(None)),
+                    name: Field::Set(FindOperator::Like("%or%")),
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
                 }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("is_handsome" = ?) AND ("father_name" IS NOT NULL)"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "name" LIKE ? ESCAPE '\'"###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
@@ -645,39 +621,25 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn multiple_conds() {
+        async fn not_like() {
             let mut manager = setup().await;
 
             let characters = Character::find(
                 &mut manager,
-                vec![
-                    CharacterSelector {
-                        id: Field::Omit,
-                        name: Field::Set(FindOperator::Eq("Legalas".into())),
-                        is_handsome: Field::Omit,
-                        father_name: Field::Omit,
-                    },
-                    CharacterSelector {
-                        id: Field::Omit,
-                        name: Field::Omit,
-                        is_handsome: Field::Set(FindOperator::Eq(false)),
-                        father_name: Field::Omit,
-                    },
-                ],
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Set(FindOperator::NotLike("%a%")),
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("name" = ?) OR ("is_handsome" = ?)"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "name" NOT LIKE ? ESCAPE '\'"###);
             assert_debug_snapshot!(characters, @r###"
             [
-                Character {
-                    id: 0,
-                    name: "Legalas",
-                    is_handsome: true,
-                    father_name: None,
-                },
                 Character {
                     id: 1,
                     name: "Himmly",
@@ -691,97 +653,106 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn finalizer_optional() {
+        async fn ilike() {
             let mut manager = setup().await;
 
-            let character = Character::find(
-                &mut manager,
-                vec![CharacterSelector {
-                    id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Golum".into())),
-                    is_handsome: Field::Omit,
-                    father_name: Field::Omit,
-                }],
-            )
-            .optional()
-            .await
-            .unwrap();
-
-            assert_debug_snapshot!(character, @"None");
-
-            let character = Character::find(
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Legalas".into())),
+                    name: Field::Set(FindOperator::ILike("LEG%")),
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
-            .optional()
+            .all()
             .await
             .unwrap();
 
-            assert_debug_snapshot!(character, @r###"
-            Some(
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE LOWER("name") LIKE LOWER(?) ESCAPE '\'"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
                 Character {
                     id: 0,
                     name: "Legalas",
                     is_handsome: true,
                     father_name: None,
                 },
-            )
+            ]
             "###);
         }
 
         #[tokio::test]
-        async fn finalizer_one() {
+        async fn like_escaped() {
             let mut manager = setup().await;
 
-            let character = Character::find(
+            manager
+                .exec_sql(
+                    "INSERT INTO \"character\" (id, name, is_handsome) VALUES (3, '100%_Orc', false)",
+                )
+                .await
+                .unwrap();
+
+            let pattern = format!("{}%", escape_like("100%_"));
+
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Golum".into())),
+                    name: Field::Set(FindOperator::Like(&pattern)),
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
-            .one()
-            .await;
+            .all()
+            .await
+            .unwrap();
 
-            assert_debug_snapshot!(character, @r###"
-            Err(
-                RowNotFound,
-            )
+            assert_snapshot!(pattern, @r###"100\%\_%"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 3,
+                    name: "100%_Orc",
+                    is_handsome: false,
+                    father_name: None,
+                },
+            ]
             "###);
+        }
 
-            let character = Character::find(
+        #[tokio::test]
+        async fn eq_null() {
+            let mut manager = setup().await;
+
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Legalas".into())),
+                    name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Omit,
+                    father_name: Field::Set(FindOperator::Eq(None)),
                 }],
             )
-            .one()
-            .await;
+            .all()
+            .await
+            .unwrap();
 
-            assert_debug_snapshot!(character, @r###"
-            Ok(
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IS NULL"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
                 Character {
                     id: 0,
                     name: "Legalas",
                     is_handsome: true,
                     father_name: None,
                 },
-            )
+            ]
             "###);
         }
 
         #[tokio::test]
-        async fn finalizer_all() {
+        async fn ne_null() {
             let mut manager = setup().await;
 
             let characters = Character::find(
@@ -790,21 +761,16 @@ mod entity {
                     id: Field::Omit,
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Omit,
+                    father_name: Field::Set(FindOperator::Ne(None)),
                 }],
             )
             .all()
             .await
             .unwrap();
 
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IS NOT NULL"###);
             assert_debug_snapshot!(characters, @r###"
             [
-                Character {
-                    id: 0,
-                    name: "Legalas",
-                    is_handsome: true,
-                    father_name: None,
-                },
                 Character {
                     id: 1,
                     name: "Himmly",
@@ -826,49 +792,31 @@ mod entity {
         }
 
         #[tokio::test]
-        async fn finalizer_stream() {
+        async fn in_with_null() {
             let mut manager = setup().await;
 
-            let mut characters = Character::find(
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Omit,
+                    father_name: Field::Set(FindOperator::In(vec![Some("Arathorn".into()), None])),
                 }],
             )
-            .stream();
+            .all()
+            .await
+            .unwrap();
 
-            let ch1 = characters.try_next().await.unwrap();
-            assert_debug_snapshot!(ch1, @r###"
-            Some(
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" IN (?) OR "father_name" IS NULL"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
                 Character {
                     id: 0,
                     name: "Legalas",
                     is_handsome: true,
                     father_name: None,
                 },
-            )
-            "###);
-
-            let ch2 = characters.try_next().await.unwrap();
-            assert_debug_snapshot!(ch2, @r###"
-            Some(
-                Character {
-                    id: 1,
-                    name: "Himmly",
-                    is_handsome: false,
-                    father_name: Some(
-                        "Gloyne",
-                    ),
-                },
-            )
-            "###);
-
-            let ch3 = characters.try_next().await.unwrap();
-            assert_debug_snapshot!(ch3, @r###"
-            Some(
                 Character {
                     id: 2,
                     name: "Aragorn",
@@ -877,47 +825,33 @@ mod entity {
                         "Arathorn",
                     ),
                 },
-            )
+            ]
             "###);
-
-            let ch4 = characters.try_next().await.unwrap();
-            assert_debug_snapshot!(ch4, @"None");
         }
-    }
-
-    mod find_with {
-        use super::*;
 
         #[tokio::test]
-        async fn no_options() {
+        async fn not_in_with_null() {
             let mut manager = setup().await;
-            let characters = Character::find_with_options(
+
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
                     name: Field::Omit,
                     is_handsome: Field::Omit,
-                    father_name: Field::Omit,
+                    father_name: Field::Set(FindOperator::NotIn(vec![
+                        Some("Arathorn".into()),
+                        None,
+                    ])),
                 }],
-                FindOptions {
-                    order_by: None,
-                    offset: None,
-                    limit: None,
-                },
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character""###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "father_name" NOT IN (?) AND "father_name" IS NOT NULL"###);
             assert_debug_snapshot!(characters, @r###"
             [
-                Character {
-                    id: 0,
-                    name: "Legalas",
-                    is_handsome: true,
-                    father_name: None,
-                },
                 Character {
                     id: 1,
                     name: "Himmly",
@@ -926,22 +860,15 @@ mod entity {
                         "Gloyne",
                     ),
                 },
-                Character {
-                    id: 2,
-                    name: "Aragorn",
-                    is_handsome: true,
-                    father_name: Some(
-                        "Arathorn",
-                    ),
-                },
             ]
             "###);
         }
 
         #[tokio::test]
-        async fn order_by() {
+        async fn empty_cond() {
             let mut manager = setup().await;
-            let characters = Character::find_with_options(
+
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -949,29 +876,19 @@ mod entity {
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
-                FindOptions {
-                    order_by: Some(OrderBy {
-                        cols: vec![CharacterCol::Id],
-                        order: Order::Desc,
-                    }),
-                    offset: None,
-                    limit: None,
-                },
             )
             .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" ORDER BY "id" DESC"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character""###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
-                    id: 2,
-                    name: "Aragorn",
+                    id: 0,
+                    name: "Legalas",
                     is_handsome: true,
-                    father_name: Some(
-                        "Arathorn",
-                    ),
+                    father_name: None,
                 },
                 Character {
                     id: 1,
@@ -982,152 +899,190 @@ mod entity {
                     ),
                 },
                 Character {
-                    id: 0,
-                    name: "Legalas",
+                    id: 2,
+                    name: "Aragorn",
                     is_handsome: true,
-                    father_name: None,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
                 },
             ]
             "###);
         }
 
-        async fn offset() {
-            // TODO
-        }
-
-        fn limit() {
-            // TODO
-        }
-
-        fn multiple_options() {
-            // TODO
-        }
-    }
-
-    mod get {
-        use super::*;
-
         #[tokio::test]
-        async fn existing() {
+        async fn multiple_fields() {
             let mut manager = setup().await;
 
-            let character = Character::get(&mut manager, 1).await.unwrap();
+            let characters = Character::find(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    father_name: Field::Set(FindOperator::Ne(None)),
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
-            assert_debug_snapshot!(character, @r###"
-            Character {
-                id: 1,
-                name: "Himmly",
-                is_handsome: false,
-                father_name: Some(
-                    "Gloyne",
-                ),
-            }
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("is_handsome" = ?) AND ("father_name" IS NOT NULL)"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+            ]
             "###);
         }
 
         #[tokio::test]
-        async fn non_existing() {
+        async fn multiple_conds() {
             let mut manager = setup().await;
 
-            let result = Character::get(&mut manager, 3).await;
-
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
-            assert_debug_snapshot!(result, @r###"
-            Err(
-                RowNotFound,
+            let characters = Character::find(
+                &mut manager,
+                vec![
+                    CharacterSelector {
+                        id: Field::Omit,
+                        name: Field::Set(FindOperator::Eq("Legalas".into())),
+                        is_handsome: Field::Omit,
+                        father_name: Field::Omit,
+                    },
+                    CharacterSelector {
+                        id: Field::Omit,
+                        name: Field::Omit,
+                        is_handsome: Field::Set(FindOperator::Eq(false)),
+                        father_name: Field::Omit,
+                    },
+                ],
             )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("name" = ?) OR ("is_handsome" = ?)"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+            ]
             "###);
         }
-    }
-
-    mod count {
-        use super::*;
 
         #[tokio::test]
-        async fn normal() {
+        async fn finalizer_optional() {
             let mut manager = setup().await;
-            let count = Character::count(
+
+            let character = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    name: Field::Set(FindOperator::Eq("Golum".into())),
+                    is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
+            .optional()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "is_handsome" = ?"###);
-            assert_debug_snapshot!(count, @"2");
-        }
+            assert_debug_snapshot!(character, @"None");
 
-        #[tokio::test]
-        async fn empty_cond() {
-            let mut manager = setup().await;
-            let count = Character::count(
+            let character = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Omit,
+                    name: Field::Set(FindOperator::Eq("Legalas".into())),
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
+            .optional()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character""###);
-            assert_debug_snapshot!(count, @"3");
+            assert_debug_snapshot!(character, @r###"
+            Some(
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+            )
+            "###);
         }
-    }
-
-    mod exists {
-        use super::*;
 
         #[tokio::test]
-        async fn existing() {
+        async fn finalizer_one() {
             let mut manager = setup().await;
-            let exists = Character::exists(
+
+            let character = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Himmly".into())),
+                    name: Field::Set(FindOperator::Eq("Golum".into())),
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
-            .await
-            .unwrap();
+            .one()
+            .await;
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "name" = ?"###);
-            assert_debug_snapshot!(exists, @"true");
-        }
+            assert_debug_snapshot!(character, @r###"
+            Err(
+                RowNotFound,
+            )
+            "###);
 
-        #[tokio::test]
-        async fn non_existing() {
-            let mut manager = setup().await;
-            let exists = Character::exists(
+            let character = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
-                    name: Field::Set(FindOperator::Eq("Gollum".into())),
+                    name: Field::Set(FindOperator::Eq("Legalas".into())),
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
             )
-            .await
-            .unwrap();
+            .one()
+            .await;
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "name" = ?"###);
-            assert_debug_snapshot!(exists, @"false");
+            assert_debug_snapshot!(character, @r###"
+            Ok(
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+            )
+            "###);
         }
 
         #[tokio::test]
-        async fn empty_cond() {
+        async fn finalizer_all() {
             let mut manager = setup().await;
-            let exists = Character::exists(
+
+            let characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1136,109 +1091,105 @@ mod entity {
                     father_name: Field::Omit,
                 }],
             )
+            .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character""###);
-            assert_debug_snapshot!(exists, @"true");
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+            ]
+            "###);
         }
-    }
-}
-
-mod create {
-    use super::*;
-
-    mod insert {
-        // TODO
-        fn normal() {}
-
-        fn partial_inputs() {}
-
-        fn empty_inputs() {}
-
-        fn different_fields_across_inputs() {}
-    }
-
-    mod create {
-        // TODO
-        use super::*;
-
-        #[tokio::test]
-        async fn normal() {
-            // TODO
-        }
-
-        fn partial_inputs() {
-            // TODO
-        }
-
-        fn empty_inputs() {
-            // TODO
-        }
-
-        fn different_fields_across_inputs() {
-            // TODO
-        }
-    }
-
-    mod create_many {
-        fn normal() {}
-
-        fn no_inputs() {}
-    }
-
-    mod persist {
-        fn normal() {}
-    }
-}
-
-mod update {
-    use super::*;
-    mod update {
-        use super::*;
 
         #[tokio::test]
-        async fn normal() {
+        async fn finalizer_stream() {
             let mut manager = setup().await;
 
-            Character::update(
+            let mut characters = Character::find(
                 &mut manager,
                 vec![CharacterSelector {
-                    id: Field::Set(FindOperator::Eq(1)),
+                    id: Field::Omit,
                     name: Field::Omit,
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
-                CharacterPatch {
-                    id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Set(true),
-                    father_name: Field::Omit,
+            )
+            .stream();
+
+            let ch1 = characters.try_next().await.unwrap();
+            assert_debug_snapshot!(ch1, @r###"
+            Some(
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
                 },
             )
-            .await
-            .unwrap();
+            "###);
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ?"###);
+            let ch2 = characters.try_next().await.unwrap();
+            assert_debug_snapshot!(ch2, @r###"
+            Some(
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+            )
+            "###);
 
-            let updated = Character::get(&mut manager, 1).await.unwrap();
-            assert_debug_snapshot!(updated, @r###"
-            Character {
-                id: 1,
-                name: "Himmly",
-                is_handsome: true,
-                father_name: Some(
-                    "Gloyne",
-                ),
-            }
+            let ch3 = characters.try_next().await.unwrap();
+            assert_debug_snapshot!(ch3, @r###"
+            Some(
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+            )
             "###);
+
+            let ch4 = characters.try_next().await.unwrap();
+            assert_debug_snapshot!(ch4, @"None");
         }
+    }
+
+    mod find_with {
+        use super::*;
 
         #[tokio::test]
-        async fn empty_cond() {
+        async fn no_options() {
             let mut manager = setup().await;
-
-            Character::update(
+            let characters = Character::find_with_options(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1246,37 +1197,24 @@ mod update {
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
-                CharacterPatch {
-                    id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Set(false),
-                    father_name: Field::Omit,
+                FindOptions {
+                    order_by: None,
+                    offset: None,
+                    limit: None,
+                    ..FindOptions::default()
                 },
             )
-            .await
-            .unwrap();
-
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ?"###);
-
-            let characters = Character::find(
-                &mut manager,
-                vec![CharacterSelector {
-                    id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Omit,
-                    father_name: Field::Omit,
-                }],
-            )
             .all()
             .await
             .unwrap();
 
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character""###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
                     id: 0,
                     name: "Legalas",
-                    is_handsome: false,
+                    is_handsome: true,
                     father_name: None,
                 },
                 Character {
@@ -1290,7 +1228,7 @@ mod update {
                 Character {
                     id: 2,
                     name: "Aragorn",
-                    is_handsome: false,
+                    is_handsome: true,
                     father_name: Some(
                         "Arathorn",
                     ),
@@ -1300,10 +1238,9 @@ mod update {
         }
 
         #[tokio::test]
-        async fn multiple_fields() {
+        async fn order_by() {
             let mut manager = setup().await;
-
-            Character::update(
+            let characters = Character::find_with_options(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1311,19 +1248,50 @@ mod update {
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
-                CharacterPatch {
-                    id: Field::Omit,
-                    name: Field::Omit,
-                    is_handsome: Field::Set(false),
-                    father_name: Field::Set(None),
+                FindOptions {
+                    order_by: Some(OrderBy::new(Order::Desc, vec![CharacterCol::Id])),
+                    offset: None,
+                    limit: None,
+                    ..FindOptions::default()
                 },
             )
+            .all()
             .await
             .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ?, "father_name" = ?"###);
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" ORDER BY "id" DESC"###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+            ]
+            "###);
+        }
 
-            let characters = Character::find(
+        #[tokio::test]
+        async fn order_by_per_col() {
+            let mut manager = setup().await;
+            let characters = Character::find_with_options(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1331,65 +1299,2061 @@ mod update {
                     is_handsome: Field::Omit,
                     father_name: Field::Omit,
                 }],
+                FindOptions {
+                    order_by: Some(OrderBy {
+                        cols: vec![
+                            OrderByCol {
+                                col: CharacterCol::FatherName,
+                                order: Order::Desc,
+                                nulls: Some(Nulls::First),
+                            },
+                            OrderByCol {
+                                col: CharacterCol::Id,
+                                order: Order::Asc,
+                                nulls: None,
+                            },
+                        ],
+                    }),
+                    offset: None,
+                    limit: None,
+                    ..FindOptions::default()
+                },
             )
             .all()
             .await
             .unwrap();
 
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" ORDER BY "father_name" DESC NULLS FIRST, "id" ASC"###);
             assert_debug_snapshot!(characters, @r###"
             [
                 Character {
                     id: 0,
                     name: "Legalas",
-                    is_handsome: false,
+                    is_handsome: true,
                     father_name: None,
                 },
                 Character {
                     id: 1,
                     name: "Himmly",
                     is_handsome: false,
-                    father_name: None,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
                 },
                 Character {
                     id: 2,
                     name: "Aragorn",
-                    is_handsome: false,
-                    father_name: None,
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
                 },
             ]
             "###);
         }
+
+        async fn offset() {
+            // TODO
+        }
+
+        fn limit() {
+            // TODO
+        }
+
+        fn multiple_options() {
+            // TODO
+        }
     }
 
-    mod patch {
+    mod find_where {
         use super::*;
 
         #[tokio::test]
         async fn normal() {
             let mut manager = setup().await;
-            let mut character = Character::get(&mut manager, 1).await.unwrap();
-            character
-                .patch(
-                    &mut manager,
-                    CharacterPatch {
-                        id: Field::Omit,
-                        name: Field::Omit,
-                        is_handsome: Field::Set(true),
-                        father_name: Field::Omit,
-                    },
-                )
-                .await
-                .unwrap();
+            let characters = Character::find_where(
+                &mut manager,
+                Filter::Or(vec![
+                    Filter::condition("name", FindOperator::Eq("Himmly")),
+                    Filter::condition("father_name", FindOperator::Eq(None::<&str>)),
+                ]),
+                FindOptions {
+                    order_by: Some(OrderBy::new(Order::Desc, vec![CharacterCol::Id])),
+                    offset: None,
+                    limit: None,
+                    ..FindOptions::default()
+                },
+            )
+            .all()
+            .await
+            .unwrap();
 
-            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ?"###);
-            assert_debug_snapshot!(character, @r###"
-            Character {
-                id: 1,
-                name: "Himmly",
-                is_handsome: true,
-                father_name: Some(
-                    "Gloyne",
-                ),
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("name" = ?) OR ("father_name" IS NULL) ORDER BY "id" DESC"###);
+            assert_eq!(
+                characters
+                    .iter()
+                    .map(|character| character.id)
+                    .collect::<Vec<_>>(),
+                vec![1, 0]
+            );
+        }
+
+        #[tokio::test]
+        async fn include() {
+            struct Parentage {
+                father_name: Option<String>,
+                mother_name: Option<String>,
+            }
+
+            impl FromRecord<Sqlite> for Parentage {
+                fn from_record(record: &Record<Sqlite>) -> Result<Self, RecordError> {
+                    Ok(Self {
+                        father_name: record.col("father_name")?,
+                        mother_name: record.try_col("mother_name")?,
+                    })
+                }
+            }
+
+            let mut manager = setup().await;
+            let pairs = Character::find_where(
+                &mut manager,
+                Filter::condition("id", FindOperator::Eq(2_u8)),
+                FindOptions {
+                    order_by: None,
+                    offset: None,
+                    limit: None,
+                    include: vec![CharacterCol::FatherName, CharacterCol::Name],
+                },
+            )
+            .with_lazy::<Parentage>()
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
+            assert_eq!(pairs.len(), 1);
+            assert_eq!(pairs[0].0.id, 2);
+            assert_eq!(pairs[0].1.father_name, Some("Arathorn".to_owned()));
+            assert_eq!(pairs[0].1.mother_name, None);
+        }
+    }
+
+    mod project {
+        use super::*;
+
+        #[derive(Debug, PartialEq)]
+        struct CharacterName {
+            id: u8,
+            name: String,
+        }
+
+        impl FromRecord<Sqlite> for CharacterName {
+            fn from_record(record: &Record<Sqlite>) -> Result<Self, RecordError> {
+                Ok(Self {
+                    id: record.col("id")?,
+                    name: record.col("name")?,
+                })
+            }
+        }
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+            let names = Character::project::<_, CharacterName>(
+                &mut manager,
+                vec![CharacterSelector {
+                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    ..Default::default()
+                }],
+                vec![CharacterCol::Id, CharacterCol::Name],
+                FindOptions {
+                    order_by: Some(OrderBy::new(Order::Desc, vec![CharacterCol::Id])),
+                    offset: None,
+                    limit: None,
+                    ..FindOptions::default()
+                },
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name" FROM "character" WHERE "is_handsome" = ? ORDER BY "id" DESC"###);
+            assert_eq!(
+                names,
+                vec![
+                    CharacterName {
+                        id: 2,
+                        name: "Aragorn".to_owned()
+                    },
+                    CharacterName {
+                        id: 0,
+                        name: "Legalas".to_owned()
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn missing_col() {
+            let mut manager = setup().await;
+            let result = Character::project_where::<_, CharacterName>(
+                &mut manager,
+                Filter::condition("id", FindOperator::Eq(1_u8)),
+                vec![CharacterCol::Id],
+                FindOptions {
+                    order_by: None,
+                    offset: None,
+                    limit: None,
+                    include: vec![CharacterCol::Id],
+                },
+            )
+            .one()
+            .await;
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id" FROM "character" WHERE "id" = ?"###);
+            assert!(result.is_err());
+        }
+    }
+
+    mod load_lazy {
+        use super::*;
+        use crate::manager::impls::mock::MockDialect;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+            let pairs = Character::load_lazy::<_, Option<String>>(
+                &mut manager,
+                "father_name",
+                vec![2, 0, 2],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "father_name" FROM "character" WHERE "id" IN (?, ?)"###);
+            assert_eq!(pairs, vec![(0, None), (2, Some("Arathorn".to_owned()))]);
+        }
+
+        #[tokio::test]
+        async fn chunked() {
+            let mut manager = setup().await.dialect(MockDialect {
+                max_parameters: 2,
+                ..MockDialect::default()
+            });
+            let pairs = Character::load_lazy::<_, Option<String>>(
+                &mut manager,
+                "father_name",
+                vec![2, 0, 1],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"
+            SELECT "id", "father_name" FROM "character" WHERE "id" IN (?, ?);
+            SELECT "id", "father_name" FROM "character" WHERE "id" IN (?)
+            "###);
+            assert_eq!(
+                pairs,
+                vec![
+                    (0, None),
+                    (2, Some("Arathorn".to_owned())),
+                    (1, Some("Gloyne".to_owned()))
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn no_ids() {
+            let mut manager = setup().await;
+            let pairs =
+                Character::load_lazy::<_, Option<String>>(&mut manager, "father_name", vec![])
+                    .await
+                    .unwrap();
+
+            assert!(pairs.is_empty());
+        }
+    }
+
+    mod find_by_keys {
+        use super::*;
+        use crate::manager::impls::mock::MockDialect;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+            let pairs = Character::find_by_keys(&mut manager, "id", vec![2_u8, 0, 2])
+                .await
+                .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" IN (?, ?)"###);
+            assert_eq!(
+                pairs
+                    .iter()
+                    .map(|(key, character)| (*key, character.name.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![(0, "Legalas"), (2, "Aragorn")]
+            );
+        }
+
+        #[tokio::test]
+        async fn chunked() {
+            let mut manager = setup().await.dialect(MockDialect {
+                max_parameters: 2,
+                ..MockDialect::default()
+            });
+            let pairs = Character::find_by_keys(&mut manager, "id", vec![2_u8, 0, 1])
+                .await
+                .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"
+            SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" IN (?, ?);
+            SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" IN (?)
+            "###);
+            assert_eq!(
+                pairs
+                    .iter()
+                    .map(|(key, character)| (*key, character.name.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![(0, "Legalas"), (2, "Aragorn"), (1, "Himmly")]
+            );
+        }
+
+        #[tokio::test]
+        async fn no_keys() {
+            let mut manager = setup().await;
+            let pairs = Character::find_by_keys(&mut manager, "id", Vec::<u8>::new())
+                .await
+                .unwrap();
+
+            assert!(pairs.is_empty());
+        }
+    }
+
+    mod find_page_after {
+        use super::*;
+        use crate::entity::SelectError;
+
+        fn ids(page: &Page<Character, (u8,)>) -> Vec<u8> {
+            page.items.iter().map(|character| character.id).collect()
+        }
+
+        #[tokio::test]
+        async fn first_page() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" ORDER BY "id" ASC LIMIT 3"###);
+            assert_eq!(ids(&page), vec![0, 1]);
+            assert!(page.next.is_some());
+            assert!(page.previous.is_none());
+        }
+
+        #[tokio::test]
+        async fn next_page() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                page.next,
+                2,
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("id") > (?) ORDER BY "id" ASC LIMIT 3"###);
+            assert_eq!(ids(&page), vec![2]);
+            assert!(page.next.is_none());
+            assert!(page.previous.is_some());
+        }
+
+        #[tokio::test]
+        async fn previous_page() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                page.next,
+                1,
+            )
+            .await
+            .unwrap();
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                page.previous,
+                1,
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("id") < (?) ORDER BY "id" DESC LIMIT 2"###);
+            assert_eq!(ids(&page), vec![0]);
+            assert!(page.next.is_some());
+            assert!(page.previous.is_none());
+        }
+
+        #[tokio::test]
+        async fn descending() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::In(vec![0, 2])),
+                    ..CharacterSelector::default()
+                }],
+                OrderBy::new(Order::Desc, vec![CharacterCol::Id]),
+                None,
+                1,
+            )
+            .await
+            .unwrap();
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::In(vec![0, 2])),
+                    ..CharacterSelector::default()
+                }],
+                OrderBy::new(Order::Desc, vec![CharacterCol::Id]),
+                page.next,
+                1,
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("id" IN (?, ?)) AND (("id") < (?)) ORDER BY "id" DESC LIMIT 2"###);
+            assert_eq!(ids(&page), vec![0]);
+            assert!(page.next.is_none());
+            assert!(page.previous.is_some());
+        }
+
+        #[tokio::test]
+        async fn mixed_orders() {
+            let mut manager = setup().await;
+            let order_by = || OrderBy {
+                cols: vec![
+                    OrderByCol {
+                        col: CharacterCol::Name,
+                        order: Order::Desc,
+                        nulls: None,
+                    },
+                    OrderByCol {
+                        col: CharacterCol::Id,
+                        order: Order::Asc,
+                        nulls: None,
+                    },
+                ],
+            };
+            let page: Page<Character, (String, u8)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                order_by(),
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                order_by(),
+                page.next,
+                2,
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("name" < ?) OR (("name" = ?) AND ("id" > ?)) ORDER BY "name" DESC, "id" ASC LIMIT 3"###);
+            assert_eq!(
+                page.items
+                    .iter()
+                    .map(|character| character.id)
+                    .collect::<Vec<_>>(),
+                vec![2]
+            );
+        }
+
+        #[tokio::test]
+        async fn previous_from_probe() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::In(vec![1, 2])),
+                    ..CharacterSelector::default()
+                }],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                Some(Cursor::after((0,))),
+                1,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(ids(&page), vec![1]);
+            assert!(page.next.is_some());
+            assert!(page.previous.is_none());
+
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::In(vec![1, 2])),
+                    ..CharacterSelector::default()
+                }],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                page.next,
+                1,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(ids(&page), vec![2]);
+            assert!(page.next.is_none());
+            assert!(page.previous.is_some());
+        }
+
+        #[tokio::test]
+        async fn next_from_probe() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                Some(Cursor::before((3,))),
+                1,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(ids(&page), vec![2]);
+            assert!(page.next.is_none());
+            assert!(page.previous.is_some());
+        }
+
+        #[tokio::test]
+        async fn nulls_order() {
+            let mut manager = setup().await;
+            let result: Result<Page<Character, (Option<String>,)>, _> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy {
+                    cols: vec![OrderByCol {
+                        col: CharacterCol::FatherName,
+                        order: Order::Asc,
+                        nulls: Some(Nulls::Last),
+                    }],
+                },
+                None,
+                1,
+            )
+            .await;
+
+            assert!(matches!(result, Err(SelectError::NullKey)));
+        }
+
+        #[tokio::test]
+        async fn null_cursor_key() {
+            let mut manager = setup().await;
+            let result: Result<Page<Character, (Option<String>,)>, _> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::FatherName]),
+                Some(Cursor::after((None,))),
+                1,
+            )
+            .await;
+
+            assert!(matches!(result, Err(SelectError::NullKey)));
+        }
+
+        #[cfg(feature = "json")]
+        #[tokio::test]
+        async fn serialized_cursor() {
+            let mut manager = setup().await;
+            let page: Page<Character, (u8,)> = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+
+            let serialized = serde_json::to_string(&page.next.unwrap()).unwrap();
+            assert_snapshot!(serialized, @r###"{"key":[1],"direction":"After"}"###);
+
+            let page = Character::find_page_after(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                OrderBy::new(Order::Asc, vec![CharacterCol::Id]),
+                Some(serde_json::from_str::<Cursor<(u8,)>>(&serialized).unwrap()),
+                2,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(ids(&page), vec![2]);
+        }
+    }
+
+    mod get {
+        use super::*;
+
+        #[tokio::test]
+        async fn existing() {
+            let mut manager = setup().await;
+
+            let character = Character::get(&mut manager, 1).await.unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
+            assert_debug_snapshot!(character, @r###"
+            Character {
+                id: 1,
+                name: "Himmly",
+                is_handsome: false,
+                father_name: Some(
+                    "Gloyne",
+                ),
+            }
+            "###);
+        }
+
+        #[tokio::test]
+        async fn non_existing() {
+            let mut manager = setup().await;
+
+            let result = Character::get(&mut manager, 3).await;
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
+            assert_debug_snapshot!(result, @r###"
+            Err(
+                RowNotFound,
+            )
+            "###);
+        }
+    }
+
+    mod count {
+        use super::*;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+            let count = Character::count(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "is_handsome" = ?"###);
+            assert_debug_snapshot!(count, @"2");
+        }
+
+        #[tokio::test]
+        async fn empty_cond() {
+            let mut manager = setup().await;
+            let count = Character::count(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character""###);
+            assert_debug_snapshot!(count, @"3");
+        }
+    }
+
+    mod aggregate {
+        use super::*;
+
+        struct Stats {
+            is_handsome: bool,
+            max_id: i64,
+            count_father_name: i64,
+        }
+
+        impl FromRecord<Sqlite> for Stats {
+            fn from_record(record: &Record<Sqlite>) -> Result<Self, RecordError> {
+                Ok(Stats {
+                    is_handsome: record.col("is_handsome")?,
+                    max_id: record.col("max_id")?,
+                    count_father_name: record.col("count_father_name")?,
+                })
+            }
+        }
+
+        struct Totals {
+            sum_id: i64,
+            avg_id: f64,
+            min_name: String,
+        }
+
+        impl FromRecord<Sqlite> for Totals {
+            fn from_record(record: &Record<Sqlite>) -> Result<Self, RecordError> {
+                Ok(Totals {
+                    sum_id: record.col("sum_id")?,
+                    avg_id: record.col("avg_id")?,
+                    min_name: record.col("min_name")?,
+                })
+            }
+        }
+
+        #[tokio::test]
+        async fn group_by() {
+            let mut manager = setup().await;
+            let mut stats: Vec<Stats> = Character::aggregate(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                vec![
+                    Aggregate {
+                        function: AggregateFunction::Max,
+                        col: CharacterCol::Id,
+                    },
+                    Aggregate {
+                        function: AggregateFunction::Count,
+                        col: CharacterCol::FatherName,
+                    },
+                ],
+                vec![CharacterCol::IsHandsome],
+            )
+            .all()
+            .await
+            .unwrap();
+            stats.sort_by_key(|stats| stats.is_handsome);
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "is_handsome", MAX("id") AS "max_id", COUNT("father_name") AS "count_father_name" FROM "character" GROUP BY "is_handsome""###);
+            assert_eq!(
+                stats
+                    .iter()
+                    .map(|stats| (stats.is_handsome, stats.max_id, stats.count_father_name))
+                    .collect::<Vec<_>>(),
+                vec![(false, 1, 1), (true, 2, 1)]
+            );
+        }
+
+        #[tokio::test]
+        async fn no_group_by() {
+            let mut manager = setup().await;
+            let totals: Totals = Character::aggregate(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::Gt(0)),
+                    ..CharacterSelector::default()
+                }],
+                vec![
+                    Aggregate {
+                        function: AggregateFunction::Sum,
+                        col: CharacterCol::Id,
+                    },
+                    Aggregate {
+                        function: AggregateFunction::Avg,
+                        col: CharacterCol::Id,
+                    },
+                    Aggregate {
+                        function: AggregateFunction::Min,
+                        col: CharacterCol::Name,
+                    },
+                ],
+                vec![],
+            )
+            .one()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT SUM("id") AS "sum_id", AVG("id") AS "avg_id", MIN("name") AS "min_name" FROM "character" WHERE "id" > ?"###);
+            assert_eq!(totals.sum_id, 3);
+            assert!((totals.avg_id - 1.5).abs() < f64::EPSILON);
+            assert_eq!(totals.min_name, "Aragorn");
+        }
+
+        #[tokio::test]
+        async fn no_aggregates() {
+            let mut manager = setup().await;
+            let result = Character::aggregate::<_, Totals>(
+                &mut manager,
+                vec![CharacterSelector::default()],
+                vec![],
+                vec![CharacterCol::IsHandsome],
+            )
+            .all()
+            .await;
+
+            assert_snapshot!(result.map(|_| ()).unwrap_err(), @"aggregate query has no aggregates");
+        }
+    }
+
+    mod exists {
+        use super::*;
+
+        #[tokio::test]
+        async fn existing() {
+            let mut manager = setup().await;
+            let exists = Character::exists(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Set(FindOperator::Eq("Himmly".into())),
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "name" = ?"###);
+            assert_debug_snapshot!(exists, @"true");
+        }
+
+        #[tokio::test]
+        async fn non_existing() {
+            let mut manager = setup().await;
+            let exists = Character::exists(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Set(FindOperator::Eq("Gollum".into())),
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE "name" = ?"###);
+            assert_debug_snapshot!(exists, @"false");
+        }
+
+        #[tokio::test]
+        async fn empty_cond() {
+            let mut manager = setup().await;
+            let exists = Character::exists(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character""###);
+            assert_debug_snapshot!(exists, @"true");
+        }
+    }
+}
+
+mod filter {
+    use super::*;
+    use crate::manager::{Filter, IntoFilter, Manager, SelectQuery};
+    use futures::TryStreamExt;
+
+    async fn find(manager: &mut MockManager, filter: Filter<'_, Sqlite>) -> Vec<Character> {
+        manager
+            .select(SelectQuery {
+                table_name: Character::table_name(),
+                col_names: Character::col_names().into(),
+                filter,
+                order_by: None,
+                offset: None,
+                limit: None,
+            })
+            .map_err(|_| ())
+            .and_then(|record| async move { Character::from_record(&record).map_err(|_| ()) })
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn and_or() {
+        let mut manager = setup().await;
+
+        let characters = find(
+            &mut manager,
+            Filter::And(vec![
+                Filter::condition("is_handsome", FindOperator::Eq(true)),
+                Filter::Or(vec![
+                    Filter::condition("id", FindOperator::Eq(0)),
+                    Filter::condition("father_name", FindOperator::Eq(Some("Arathorn"))),
+                ]),
+            ]),
+        )
+        .await;
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("is_handsome" = ?) AND (("id" = ?) OR ("father_name" = ?))"###);
+        assert_debug_snapshot!(characters, @r###"
+        [
+            Character {
+                id: 0,
+                name: "Legalas",
+                is_handsome: true,
+                father_name: None,
+            },
+            Character {
+                id: 2,
+                name: "Aragorn",
+                is_handsome: true,
+                father_name: Some(
+                    "Arathorn",
+                ),
+            },
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn not() {
+        let mut manager = setup().await;
+
+        let characters = find(
+            &mut manager,
+            Filter::Not(Box::new(
+                CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    father_name: Field::Omit,
+                }
+                .into_filter(),
+            )),
+        )
+        .await;
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE NOT ("is_handsome" = ?)"###);
+        assert_debug_snapshot!(characters, @r###"
+        [
+            Character {
+                id: 1,
+                name: "Himmly",
+                is_handsome: false,
+                father_name: Some(
+                    "Gloyne",
+                ),
+            },
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn null_values() {
+        let mut manager = setup().await;
+
+        let characters = find(
+            &mut manager,
+            Filter::And(vec![
+                Filter::condition("father_name", FindOperator::In(vec![None, Some("Gloyne")])),
+                Filter::condition("id", FindOperator::Ne(1)),
+            ]),
+        )
+        .await;
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("father_name" IN (?) OR "father_name" IS NULL) AND ("id" != ?)"###);
+        assert_debug_snapshot!(characters, @r###"
+        [
+            Character {
+                id: 0,
+                name: "Legalas",
+                is_handsome: true,
+                father_name: None,
+            },
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn matches_nothing() {
+        let mut manager = setup().await;
+
+        let characters = find(
+            &mut manager,
+            Filter::And(vec![
+                Filter::condition("is_handsome", FindOperator::Eq(true)),
+                Filter::condition("id", FindOperator::<u8>::In(vec![])),
+            ]),
+        )
+        .await;
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE ("is_handsome" = ?) AND (1 = 0)"###);
+        assert_debug_snapshot!(characters, @"[]");
+    }
+}
+
+mod create {
+    use super::*;
+
+    mod insert {
+        // TODO
+        fn normal() {}
+
+        fn partial_inputs() {}
+
+        fn empty_inputs() {}
+
+        fn different_fields_across_inputs() {}
+    }
+
+    mod create {
+        // TODO
+        use super::*;
+        use crate::manager::{impls::mock::MockDialect, DialectManager};
+        use sqlx::{sqlite::SqliteConnectOptions, ConnectOptions, Executor, SqliteConnection};
+        use std::str::FromStr;
+
+        #[tokio::test]
+        async fn normal() {
+            // TODO
+        }
+
+        async fn connect_without_returning() -> SqliteConnection {
+            let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE \"character\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, is_handsome BOOL NOT NULL, father_name TEXT DEFAULT 'Denethor')",
+            )
+            .await
+            .unwrap();
+            conn.execute(
+                "INSERT INTO \"character\" (id, name, is_handsome) VALUES (4, 'Boromir', 1)",
+            )
+            .await
+            .unwrap();
+            conn
+        }
+
+        fn without_returning_dialect() -> MockDialect {
+            MockDialect {
+                supports_returning: false,
+                ..MockDialect::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn without_returning() {
+            let mut conn = connect_without_returning().await;
+
+            let characters = Character::create_many(
+                DialectManager::new(&mut conn, without_returning_dialect()),
+                vec![
+                    CharacterInput {
+                        id: Field::Omit,
+                        name: "Legalas",
+                        is_handsome: true,
+                        father_name: Field::Set(None),
+                    },
+                    CharacterInput {
+                        id: Field::Omit,
+                        name: "Aragorn",
+                        is_handsome: true,
+                        father_name: Field::Set(Some("Arathorn")),
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 5,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: None,
+                },
+                Character {
+                    id: 6,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+            ]
+            "###);
+
+            let character = Character::create(
+                DialectManager::new(&mut conn, without_returning_dialect()),
+                CharacterInput {
+                    id: Field::Omit,
+                    name: "Faramir",
+                    is_handsome: true,
+                    father_name: Field::Omit,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(character, @r###"
+            Character {
+                id: 7,
+                name: "Faramir",
+                is_handsome: true,
+                father_name: Some(
+                    "Denethor",
+                ),
+            }
+            "###);
+        }
+
+        #[tokio::test]
+        async fn without_returning_mixed_keys() {
+            let mut conn = connect_without_returning().await;
+
+            let characters = Character::create_many(
+                DialectManager::new(&mut conn, without_returning_dialect()),
+                vec![
+                    CharacterInput {
+                        id: Field::Set(10),
+                        name: "Legalas",
+                        is_handsome: true,
+                        father_name: Field::Set(Some("Thranduil")),
+                    },
+                    CharacterInput {
+                        id: Field::Omit,
+                        name: "Faramir",
+                        is_handsome: true,
+                        father_name: Field::Omit,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 10,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Thranduil",
+                    ),
+                },
+                Character {
+                    id: 11,
+                    name: "Faramir",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Denethor",
+                    ),
+                },
+            ]
+            "###);
+        }
+
+        #[tokio::test]
+        async fn without_returning_gaps() {
+            let mut conn = connect_without_returning().await;
+            conn.execute(
+                "CREATE TRIGGER \"heir\" AFTER INSERT ON \"character\" WHEN NEW.name = 'Aragorn' BEGIN INSERT INTO \"character\" (name, is_handsome) VALUES ('Eldarion', 1); END",
+            )
+            .await
+            .unwrap();
+
+            let characters = Character::create_many(
+                DialectManager::new(&mut conn, without_returning_dialect()),
+                vec![
+                    CharacterInput {
+                        id: Field::Omit,
+                        name: "Aragorn",
+                        is_handsome: true,
+                        father_name: Field::Set(Some("Arathorn")),
+                    },
+                    CharacterInput {
+                        id: Field::Omit,
+                        name: "Faramir",
+                        is_handsome: true,
+                        father_name: Field::Omit,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 5,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+                Character {
+                    id: 7,
+                    name: "Faramir",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Denethor",
+                    ),
+                },
+            ]
+            "###);
+        }
+
+        #[tokio::test]
+        async fn without_returning_explicit_key() {
+            let mut conn = connect_without_returning().await;
+
+            let result = Character::create(
+                DialectManager::new(&mut conn, without_returning_dialect()),
+                CharacterInput {
+                    id: Field::Set(10),
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: Field::Omit,
+                },
+            )
+            .await;
+
+            assert!(result.is_err());
+
+            let count = Character::count(&mut conn, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_eq!(count, 1);
+        }
+
+        #[tokio::test]
+        async fn log_manager_dialect() {
+            use crate::manager::{DialectManager, LogManager, MySqlDialect};
+            use sqlx::{sqlite::SqliteConnectOptions, ConnectOptions, Executor};
+            use std::str::FromStr;
+
+            let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE \"character\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, is_handsome BOOL NOT NULL, father_name TEXT DEFAULT NULL)",
+            )
+            .await
+            .unwrap();
+
+            let mut log = String::new();
+            let character = Character::create(
+                LogManager::new(DialectManager::new(&mut conn, MySqlDialect), &mut log),
+                CharacterInput {
+                    id: Field::Omit,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: Field::Omit,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(log, @"INSERT INTO `character` (`name`, `is_handsome`) VALUES (?, ?)");
+            assert_eq!(character.id, 1);
+        }
+
+        fn partial_inputs() {
+            // TODO
+        }
+
+        fn empty_inputs() {
+            // TODO
+        }
+
+        fn different_fields_across_inputs() {
+            // TODO
+        }
+    }
+
+    mod create_many {
+        use super::*;
+        use crate::manager::{impls::mock::MockDialect, DialectManager, LogManager};
+        use sqlx::{sqlite::SqliteConnectOptions, ConnectOptions, Executor, SqliteConnection};
+        use std::str::FromStr;
+
+        fn normal() {}
+
+        fn no_inputs() {}
+
+        fn small_batch_dialect() -> MockDialect {
+            MockDialect {
+                max_parameters: 8,
+                ..MockDialect::default()
+            }
+        }
+
+        async fn connect() -> SqliteConnection {
+            let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE \"character\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, is_handsome BOOL NOT NULL, father_name TEXT DEFAULT NULL)",
+            )
+            .await
+            .unwrap();
+            conn
+        }
+
+        fn inputs(ids: &[u8]) -> Vec<CharacterInput<'static>> {
+            ids.iter()
+                .map(|id| CharacterInput {
+                    id: Field::Set(*id),
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: Field::Set(None),
+                })
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn chunked() {
+            let mut conn = connect().await;
+
+            let characters = Character::create_many(
+                DialectManager::new(&mut conn, small_batch_dialect()),
+                inputs(&[3, 1, 2]),
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters.iter().map(|character| character.id).collect::<Vec<_>>(), @r###"
+            [
+                3,
+                1,
+                2,
+            ]
+            "###);
+
+            let count = Character::count(&mut conn, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"3");
+        }
+
+        #[tokio::test]
+        async fn chunked_log() {
+            let mut conn = connect().await;
+            let mut log = String::new();
+
+            Character::create_many(
+                LogManager::new(
+                    DialectManager::new(&mut conn, small_batch_dialect()),
+                    &mut log,
+                ),
+                inputs(&[3, 1, 2]),
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(log, @r###"
+            INSERT INTO "character" ("id", "name", "is_handsome", "father_name") VALUES (?, ?, ?, ?), (?, ?, ?, ?) RETURNING "father_name";
+            INSERT INTO "character" ("id", "name", "is_handsome", "father_name") VALUES (?, ?, ?, ?) RETURNING "father_name"
+            "###);
+        }
+
+        #[tokio::test]
+        async fn chunked_rollback() {
+            let mut conn = connect().await;
+
+            let result = Character::create_many(
+                DialectManager::new(&mut conn, small_batch_dialect()),
+                inputs(&[1, 2, 1]),
+            )
+            .await;
+
+            assert!(result.is_err());
+
+            let count = Character::count(&mut conn, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"0");
+        }
+
+        #[tokio::test]
+        async fn chunked_without_transaction() {
+            let mut conn = connect().await;
+
+            let result = Character::create_many(
+                DialectManager::new(&mut conn, small_batch_dialect()).transactional_chunks(false),
+                inputs(&[1, 2, 1]),
+            )
+            .await;
+
+            assert!(result.is_err());
+
+            let count = Character::count(&mut conn, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"2");
+        }
+    }
+
+    mod insert_unnest {
+        use super::*;
+        use crate::manager::{InsertQuery, InsertReturningQuery, Manager, UnnestInsertQuery};
+        use futures::TryStreamExt;
+
+        #[tokio::test]
+        async fn falls_back_without_unnest() {
+            let mut manager = setup().await;
+
+            let mut values = InputRecord::default();
+            values.add_col("name", Box::new("Boromir"));
+            values.add_col("is_handsome", Box::new(true));
+
+            let records = (&mut manager)
+                .insert_unnest(UnnestInsertQuery {
+                    insert_returning_query: InsertReturningQuery {
+                        insert_query: InsertQuery {
+                            table_name: "character",
+                            values: vec![values],
+                        },
+                        returning_cols: &["id", "father_name"],
+                        generated_key: Some("id"),
+                    },
+                    columns: InputRecord::default(),
+                })
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "character" ("name", "is_handsome") VALUES (?, ?) RETURNING "id", "father_name""###);
+            assert_eq!(records[0].col::<u8>("id").unwrap(), 3);
+        }
+    }
+
+    mod insert_stream {
+        use super::*;
+        use futures::{stream, StreamExt};
+        use sqlx::{sqlite::SqliteConnectOptions, Acquire, ConnectOptions, Executor};
+        use std::str::FromStr;
+
+        fn inputs(ids: &[u8]) -> Vec<CharacterInput<'static>> {
+            ids.iter()
+                .map(|id| CharacterInput {
+                    id: Field::Set(*id),
+                    name: "Boromir",
+                    is_handsome: true,
+                    father_name: Field::Set(Some("Denethor")),
+                })
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn batches() {
+            let mut manager = setup().await;
+            let progress =
+                Character::insert_stream(&mut manager, stream::iter(inputs(&[3, 4, 5, 6, 7])), 2)
+                    .map(Result::unwrap)
+                    .collect::<Vec<_>>()
+                    .await;
+
+            assert_debug_snapshot!(progress, @r###"
+            [
+                InsertProgress {
+                    batch: 0,
+                    rows: 2,
+                    total_rows: 2,
+                },
+                InsertProgress {
+                    batch: 1,
+                    rows: 2,
+                    total_rows: 4,
+                },
+                InsertProgress {
+                    batch: 2,
+                    rows: 1,
+                    total_rows: 5,
+                },
+            ]
+            "###);
+            assert_snapshot!(manager.last_statement().unwrap(), @"COMMIT");
+
+            let count = Character::count(&mut manager, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"8");
+        }
+
+        #[tokio::test]
+        async fn batch_error() {
+            let mut manager = setup().await;
+            let results =
+                Character::insert_stream(&mut manager, stream::iter(inputs(&[3, 4, 1, 5, 6])), 2)
+                    .map(|result| result.map_err(|err| err.to_string()))
+                    .collect::<Vec<_>>()
+                    .await;
+
+            assert_debug_snapshot!(results, @r###"
+            [
+                Ok(
+                    InsertProgress {
+                        batch: 0,
+                        rows: 2,
+                        total_rows: 2,
+                    },
+                ),
+                Err(
+                    "failed to insert batch 1 of 2 rows",
+                ),
+                Ok(
+                    InsertProgress {
+                        batch: 2,
+                        rows: 1,
+                        total_rows: 3,
+                    },
+                ),
+            ]
+            "###);
+
+            let count = Character::count(&mut manager, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"6");
+        }
+
+        #[tokio::test]
+        async fn outer_transaction() {
+            let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            conn.execute(
+                "CREATE TABLE \"character\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, is_handsome BOOL NOT NULL, father_name TEXT DEFAULT NULL)",
+            )
+            .await
+            .unwrap();
+
+            let mut transaction = conn.begin().await.unwrap();
+            let progress =
+                Character::insert_stream(&mut *transaction, stream::iter(inputs(&[3, 4, 5])), 2)
+                    .collect::<Vec<_>>()
+                    .await;
+            transaction.rollback().await.unwrap();
+
+            assert!(progress.iter().all(Result::is_ok));
+
+            let count = Character::count(&mut conn, vec![CharacterSelector::default()])
+                .await
+                .unwrap();
+
+            assert_debug_snapshot!(count, @"0");
+        }
+    }
+
+    mod persist {
+        fn normal() {}
+    }
+
+    mod upsert {
+        use super::*;
+
+        #[tokio::test]
+        async fn update() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::upsert(
+                &mut manager,
+                CharacterInput {
+                    id: Field::Set(1),
+                    name: "Gimli",
+                    is_handsome: false,
+                    father_name: Field::Set(Some("Gloin")),
+                },
+                OnConflict {
+                    target: ConflictTarget::Cols(vec![CharacterCol::Id]),
+                    action: ConflictAction::Update(vec![
+                        CharacterCol::Name,
+                        CharacterCol::FatherName,
+                    ]),
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "character" ("id", "name", "is_handsome", "father_name") VALUES (?, ?, ?, ?) ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name", "father_name" = EXCLUDED."father_name""###);
+            assert_debug_snapshot!(rows_affected, @"1");
+
+            let character = Character::get(&mut manager, 1).await.unwrap();
+
+            assert_debug_snapshot!(character, @r###"
+            Character {
+                id: 1,
+                name: "Gimli",
+                is_handsome: false,
+                father_name: Some(
+                    "Gloin",
+                ),
+            }
+            "###);
+        }
+
+        #[tokio::test]
+        async fn do_nothing() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::upsert(
+                &mut manager,
+                CharacterInput {
+                    id: Field::Set(1),
+                    name: "Gimli",
+                    is_handsome: false,
+                    father_name: Field::Omit,
+                },
+                OnConflict {
+                    target: ConflictTarget::Cols(vec![CharacterCol::Id]),
+                    action: ConflictAction::DoNothing,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "character" ("id", "name", "is_handsome") VALUES (?, ?, ?) ON CONFLICT ("id") DO NOTHING"###);
+            assert_debug_snapshot!(rows_affected, @"0");
+
+            let character = Character::get(&mut manager, 1).await.unwrap();
+
+            assert_debug_snapshot!(character.name, @r###""Himmly""###);
+        }
+
+        #[tokio::test]
+        async fn many() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::upsert_many(
+                &mut manager,
+                vec![
+                    CharacterInput {
+                        id: Field::Set(2),
+                        name: "Strider",
+                        is_handsome: true,
+                        father_name: Field::Omit,
+                    },
+                    CharacterInput {
+                        id: Field::Set(3),
+                        name: "Boromir",
+                        is_handsome: true,
+                        father_name: Field::Omit,
+                    },
+                ],
+                OnConflict {
+                    target: ConflictTarget::Cols(vec![CharacterCol::Id]),
+                    action: ConflictAction::Update(vec![CharacterCol::Name]),
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "character" ("id", "name", "is_handsome") VALUES (?, ?, ?), (?, ?, ?) ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name""###);
+            assert_debug_snapshot!(rows_affected, @"2");
+
+            let characters = Character::find(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::In(vec![2, 3])),
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 2,
+                    name: "Strider",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+                Character {
+                    id: 3,
+                    name: "Boromir",
+                    is_handsome: true,
+                    father_name: None,
+                },
+            ]
+            "###);
+        }
+
+        #[tokio::test]
+        async fn no_inputs() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::upsert_many(
+                &mut manager,
+                vec![],
+                OnConflict {
+                    target: ConflictTarget::Cols(vec![CharacterCol::Id]),
+                    action: ConflictAction::DoNothing,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(rows_affected, @"0");
+        }
+
+        #[tokio::test]
+        async fn unsupported_constraint_target() {
+            let mut manager = setup().await;
+
+            let result = Character::upsert(
+                &mut manager,
+                CharacterInput {
+                    id: Field::Set(3),
+                    name: "Boromir",
+                    is_handsome: true,
+                    father_name: Field::Omit,
+                },
+                OnConflict {
+                    target: ConflictTarget::Constraint("character_pkey"),
+                    action: ConflictAction::DoNothing,
+                },
+            )
+            .await;
+
+            assert_debug_snapshot!(result.map_err(|err| err.to_string()), @r###"
+            Err(
+                "dialect does not support named constraints as conflict targets",
+            )
+            "###);
+        }
+    }
+}
+
+mod update {
+    use super::*;
+    mod update {
+        use super::*;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::update(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::Eq(1)),
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+                CharacterPatch {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(true),
+                    father_name: Field::Omit,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ?"###);
+            assert_debug_snapshot!(rows_affected, @"1");
+
+            let updated = Character::get(&mut manager, 1).await.unwrap();
+            assert_debug_snapshot!(updated, @r###"
+            Character {
+                id: 1,
+                name: "Himmly",
+                is_handsome: true,
+                father_name: Some(
+                    "Gloyne",
+                ),
+            }
+            "###);
+        }
+
+        #[tokio::test]
+        async fn empty_cond() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::update(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+                CharacterPatch {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(false),
+                    father_name: Field::Omit,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ?"###);
+            assert_debug_snapshot!(rows_affected, @"3");
+
+            let characters = Character::find(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: false,
+                    father_name: None,
+                },
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Arathorn",
+                    ),
+                },
+            ]
+            "###);
+        }
+
+        #[tokio::test]
+        async fn multiple_fields() {
+            let mut manager = setup().await;
+
+            let rows_affected = Character::update(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+                CharacterPatch {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(false),
+                    father_name: Field::Set(None),
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ?, "father_name" = ?"###);
+            assert_debug_snapshot!(rows_affected, @"3");
+
+            let characters = Character::find(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: false,
+                    father_name: None,
+                },
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: None,
+                },
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: false,
+                    father_name: None,
+                },
+            ]
+            "###);
+        }
+    }
+
+    mod update_returning {
+        use super::*;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+
+            let characters = Character::update_returning(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(FindOperator::Eq(true)),
+                    father_name: Field::Omit,
+                }],
+                CharacterPatch {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Set(Some("Thranduil")),
+                },
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "father_name" = ? WHERE "is_handsome" = ? RETURNING "id", "name", "is_handsome", "father_name""###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 0,
+                    name: "Legalas",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Thranduil",
+                    ),
+                },
+                Character {
+                    id: 2,
+                    name: "Aragorn",
+                    is_handsome: true,
+                    father_name: Some(
+                        "Thranduil",
+                    ),
+                },
+            ]
+            "###);
+        }
+
+        #[tokio::test]
+        async fn no_match() {
+            let mut manager = setup().await;
+
+            let characters = Character::update_returning(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Set(FindOperator::Eq(3)),
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+                CharacterPatch {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(true),
+                    father_name: Field::Omit,
+                },
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ? RETURNING "id", "name", "is_handsome", "father_name""###);
+            assert_debug_snapshot!(characters, @"[]");
+        }
+    }
+
+    mod patch {
+        use super::*;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+            let mut character = Character::get(&mut manager, 1).await.unwrap();
+            character
+                .patch(
+                    &mut manager,
+                    CharacterPatch {
+                        id: Field::Omit,
+                        name: Field::Omit,
+                        is_handsome: Field::Set(true),
+                        father_name: Field::Omit,
+                    },
+                )
+                .await
+                .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ?"###);
+            assert_debug_snapshot!(character, @r###"
+            Character {
+                id: 1,
+                name: "Himmly",
+                is_handsome: true,
+                father_name: Some(
+                    "Gloyne",
+                ),
             }
             "###);
             let updated_character = Character::get(&mut manager, 1).await.unwrap();
@@ -1404,6 +3368,46 @@ mod update {
             }
             "###);
         }
+
+        #[tokio::test]
+        async fn non_existing() {
+            let mut manager = setup().await;
+            let mut character = Character::get(&mut manager, 1).await.unwrap();
+            character.remove(&mut manager).await.unwrap();
+
+            let result = character
+                .patch(
+                    &mut manager,
+                    CharacterPatch {
+                        id: Field::Omit,
+                        name: Field::Omit,
+                        is_handsome: Field::Set(true),
+                        father_name: Field::Omit,
+                    },
+                )
+                .await;
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"UPDATE "character" SET "is_handsome" = ? WHERE "id" = ?"###);
+            assert_debug_snapshot!(result, @r###"
+            Err(
+                RowNotFound,
+            )
+            "###);
+            assert_debug_snapshot!(character.is_handsome, @"false");
+        }
+
+        #[tokio::test]
+        async fn empty_patch() {
+            let mut manager = setup().await;
+            let mut character = Character::get(&mut manager, 1).await.unwrap();
+
+            character
+                .patch(&mut manager, CharacterPatch::default())
+                .await
+                .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" = ?"###);
+        }
     }
 }
 
@@ -1417,7 +3421,7 @@ mod delete {
         async fn normal() {
             let mut manager = setup().await;
 
-            Character::delete(
+            let rows_affected = Character::delete(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1430,6 +3434,7 @@ mod delete {
             .unwrap();
 
             assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "character" WHERE "is_handsome" = ?"###);
+            assert_debug_snapshot!(rows_affected, @"2");
 
             let count = Character::count(
                 &mut manager,
@@ -1450,7 +3455,7 @@ mod delete {
         async fn empty_cond() {
             let mut manager = setup().await;
 
-            Character::delete(
+            let rows_affected = Character::delete(
                 &mut manager,
                 vec![CharacterSelector {
                     id: Field::Omit,
@@ -1463,6 +3468,7 @@ mod delete {
             .unwrap();
 
             assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "character""###);
+            assert_debug_snapshot!(rows_affected, @"3");
 
             let count = Character::count(
                 &mut manager,
@@ -1480,6 +3486,77 @@ mod delete {
         }
     }
 
+    mod delete_returning {
+        use super::*;
+
+        #[tokio::test]
+        async fn normal() {
+            let mut manager = setup().await;
+
+            let characters = Character::delete_returning(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Set(FindOperator::Eq(false)),
+                    father_name: Field::Omit,
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "character" WHERE "is_handsome" = ? RETURNING "id", "name", "is_handsome", "father_name""###);
+            assert_debug_snapshot!(characters, @r###"
+            [
+                Character {
+                    id: 1,
+                    name: "Himmly",
+                    is_handsome: false,
+                    father_name: Some(
+                        "Gloyne",
+                    ),
+                },
+            ]
+            "###);
+
+            let count = Character::count(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .await
+            .unwrap();
+
+            assert_debug_snapshot!(count, @"2");
+        }
+
+        #[tokio::test]
+        async fn empty_cond() {
+            let mut manager = setup().await;
+
+            let characters = Character::delete_returning(
+                &mut manager,
+                vec![CharacterSelector {
+                    id: Field::Omit,
+                    name: Field::Omit,
+                    is_handsome: Field::Omit,
+                    father_name: Field::Omit,
+                }],
+            )
+            .all()
+            .await
+            .unwrap();
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "character" RETURNING "id", "name", "is_handsome", "father_name""###);
+            assert_debug_snapshot!(characters.len(), @"3");
+        }
+    }
+
     mod remove {
         use super::*;
 
@@ -1506,5 +3583,527 @@ mod delete {
 
             assert_debug_snapshot!(count, @"2");
         }
+
+        #[tokio::test]
+        async fn non_existing() {
+            let mut manager = setup().await;
+
+            let character = Character::get(&mut manager, 1).await.unwrap();
+            character.remove(&mut manager).await.unwrap();
+            let result = character.remove(&mut manager).await;
+
+            assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "character" WHERE "id" = ?"###);
+            assert_debug_snapshot!(result, @r###"
+            Err(
+                RowNotFound,
+            )
+            "###);
+        }
+    }
+}
+
+mod exists {
+    use super::*;
+    use crate::{entity::exists, manager::Filter};
+
+    struct Weapon;
+
+    impl Entity<Sqlite> for Weapon {
+        type Col = CharacterCol;
+        type Id = u8;
+        type Selector<'q> = WeaponSelector<'q>;
+
+        fn table_name() -> &'static str {
+            "weapon"
+        }
+
+        fn col_names() -> &'static [&'static str] {
+            &["id"]
+        }
+
+        fn id_col_name() -> &'static str {
+            "id"
+        }
+
+        fn id(&self) -> Self::Id {
+            0
+        }
+    }
+
+    impl FromRecord<Sqlite> for Weapon {
+        fn from_record(_: &Record<Sqlite>) -> Result<Self, RecordError> {
+            Ok(Weapon)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct WeaponSelector<'q> {
+        name: Field<FindOperator<&'q str>>,
+    }
+
+    impl<'q> IntoSelector<'q, Sqlite> for WeaponSelector<'q> {
+        fn into_selector(self) -> Selector<'q, Sqlite> {
+            let mut selector = Selector::new();
+
+            if let Field::Set(op) = self.name {
+                selector.add_col("name", op.boxed());
+            }
+
+            selector
+        }
+    }
+
+    async fn setup_weapons() -> MockManager {
+        let mut manager = setup().await;
+        manager
+            .exec_sql(
+                "CREATE TABLE \"weapon\" (id INTEGER PRIMARY KEY, owner_id INTEGER NOT NULL, name TEXT NOT NULL)",
+            )
+            .await
+            .unwrap();
+        manager
+            .exec_sql(
+                "INSERT INTO \"weapon\" VALUES (0, 0, 'Bow'), (1, 1, 'Axe'), (2, 2, 'Anduril')",
+            )
+            .await
+            .unwrap();
+
+        manager
+    }
+
+    #[tokio::test]
+    async fn find() {
+        let mut manager = setup_weapons().await;
+        let characters = Character::find_where(
+            &mut manager,
+            exists::<Sqlite, Character, Weapon>(
+                "id",
+                "owner_id",
+                vec![WeaponSelector {
+                    name: Field::Set(FindOperator::Like("A%")),
+                }],
+            ),
+            FindOptions {
+                order_by: None,
+                offset: None,
+                limit: None,
+                ..FindOptions::default()
+            },
+        )
+        .all()
+        .await
+        .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE EXISTS (SELECT 1 FROM "weapon" AS "t1" WHERE "t1"."owner_id" = "character"."id" AND ("name" LIKE ? ESCAPE '\'))"###);
+        assert_eq!(
+            characters
+                .iter()
+                .map(|character| character.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn count() {
+        let mut manager = setup_weapons().await;
+        let count = Character::count_where(
+            &mut manager,
+            Filter::And(vec![
+                Filter::condition("is_handsome", FindOperator::Eq(true)),
+                exists::<Sqlite, Character, Weapon>(
+                    "id",
+                    "owner_id",
+                    vec![WeaponSelector::default()],
+                ),
+            ]),
+        )
+        .await
+        .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "character" WHERE ("is_handsome" = ?) AND (EXISTS (SELECT 1 FROM "weapon" AS "t1" WHERE "t1"."owner_id" = "character"."id"))"###);
+        assert_eq!(count, 2);
+    }
+}
+
+mod join_table {
+    use super::*;
+    use crate::{
+        entity::{JoinTable, SyncError},
+        manager::{Begin, Transaction},
+    };
+
+    const FRIENDS: JoinTable = JoinTable {
+        table_name: "friendship",
+        col_name: "character_id",
+        target_col_name: "friend_id",
+    };
+
+    async fn setup_friends() -> (MockManager, Character) {
+        let mut manager = setup().await;
+        manager
+            .exec_sql(
+                "CREATE TABLE \"friendship\" (character_id INTEGER NOT NULL, friend_id INTEGER NOT NULL)",
+            )
+            .await
+            .unwrap();
+        manager
+            .exec_sql("INSERT INTO \"friendship\" VALUES (0, 1)")
+            .await
+            .unwrap();
+        let character = Character::get(&mut manager, 0).await.unwrap();
+
+        (manager, character)
+    }
+
+    async fn friend_ids(manager: &mut MockManager, character: &Character) -> Vec<u8> {
+        FRIENDS
+            .find::<_, _, Character, Character>(manager, character)
+            .all()
+            .await
+            .unwrap()
+            .iter()
+            .map(|friend| friend.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn find() {
+        let (mut manager, character) = setup_friends().await;
+        let ids = friend_ids(&mut manager, &character).await;
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "is_handsome", "father_name" FROM "character" WHERE "id" IN (SELECT "friend_id" FROM "friendship" WHERE "character_id" = ?)"###);
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn attach() {
+        let (mut manager, character) = setup_friends().await;
+        FRIENDS
+            .attach::<_, _, Character, Character>(&mut manager, &character, vec![2])
+            .await
+            .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "friendship" ("character_id", "friend_id") VALUES (?, ?)"###);
+        assert_eq!(friend_ids(&mut manager, &character).await, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn detach() {
+        let (mut manager, character) = setup_friends().await;
+        let rows_affected = FRIENDS
+            .detach::<_, _, Character, Character>(&mut manager, &character, vec![1, 2])
+            .await
+            .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"DELETE FROM "friendship" WHERE ("character_id" = ?) AND ("friend_id" IN (?, ?))"###);
+        assert_eq!(rows_affected, 1);
+        assert!(friend_ids(&mut manager, &character).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sync() {
+        let (mut manager, character) = setup_friends().await;
+
+        let mut transaction = (&mut manager).begin().await.unwrap();
+        FRIENDS
+            .sync::<_, _, Character, Character>(&mut transaction, &character, vec![0, 2])
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        assert_eq!(friend_ids(&mut manager, &character).await, vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn sync_existing() {
+        let (mut manager, character) = setup_friends().await;
+
+        let mut transaction = (&mut manager).begin().await.unwrap();
+        FRIENDS
+            .sync::<_, _, Character, Character>(&mut transaction, &character, vec![1, 2, 2])
+            .await
+            .unwrap();
+        FRIENDS
+            .sync::<_, _, Character, Character>(&mut transaction, &character, vec![1, 2])
+            .await
+            .unwrap();
+
+        assert_snapshot!(transaction.last_statement().unwrap(), @"RELEASE SAVEPOINT _sqlx_savepoint_1");
+
+        let rows_affected = FRIENDS
+            .detach::<_, _, Character, Character>(&mut transaction, &character, vec![1, 2])
+            .await
+            .unwrap();
+
+        assert_eq!(rows_affected, 2);
+    }
+
+    #[tokio::test]
+    async fn sync_rollback() {
+        let (mut manager, character) = setup_friends().await;
+        manager
+            .exec_sql("CREATE TRIGGER no_self_friends BEFORE INSERT ON \"friendship\" WHEN NEW.friend_id = NEW.character_id BEGIN SELECT RAISE(ABORT, 'no self friends'); END")
+            .await
+            .unwrap();
+
+        let mut transaction = (&mut manager).begin().await.unwrap();
+        let result = FRIENDS
+            .sync::<_, _, Character, Character>(&mut transaction, &character, vec![0, 2])
+            .await;
+
+        assert!(matches!(result, Err(SyncError::Manager(_))));
+        assert_snapshot!(transaction.last_statement().unwrap(), @"ROLLBACK TO SAVEPOINT _sqlx_savepoint_1");
+
+        transaction.commit().await.unwrap();
+
+        assert_eq!(friend_ids(&mut manager, &character).await, vec![1]);
+    }
+}
+
+mod transaction {
+    use super::*;
+    use crate::manager::{Begin, Transaction};
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn commit() {
+        let mut manager = setup().await;
+
+        (&mut manager)
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    let mut character = Character::get(transaction.manager(), 1).await?;
+                    character
+                        .patch(
+                            transaction.manager(),
+                            CharacterPatch {
+                                id: Field::Omit,
+                                name: Field::Omit,
+                                is_handsome: Field::Set(true),
+                                father_name: Field::Omit,
+                            },
+                        )
+                        .await?;
+                    Character::create(
+                        transaction.manager(),
+                        CharacterInput {
+                            id: Field::Set(3),
+                            name: "Boromir",
+                            is_handsome: true,
+                            father_name: Field::Set(Some("Denethor")),
+                        },
+                    )
+                    .await?;
+                    Ok::<_, Box<dyn Error + Send + Sync>>(())
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @"COMMIT");
+
+        let count = Character::count(
+            &mut manager,
+            vec![CharacterSelector {
+                id: Field::Omit,
+                name: Field::Omit,
+                is_handsome: Field::Set(FindOperator::Eq(true)),
+                father_name: Field::Omit,
+            }],
+        )
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(count, @"4");
+    }
+
+    #[tokio::test]
+    async fn rollback_on_error() {
+        let mut manager = setup().await;
+
+        let result = (&mut manager)
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    Character::delete(
+                        transaction.manager(),
+                        vec![CharacterSelector {
+                            id: Field::Omit,
+                            name: Field::Omit,
+                            is_handsome: Field::Omit,
+                            father_name: Field::Omit,
+                        }],
+                    )
+                    .await?;
+                    Character::get(transaction.manager(), 1).await?;
+                    Ok::<_, Box<dyn Error + Send + Sync>>(())
+                })
+            })
+            .await;
+
+        assert_debug_snapshot!(result.map_err(|err| err.to_string()), @r###"
+        Err(
+            "query returned less rows than expected",
+        )
+        "###);
+        assert_snapshot!(manager.last_statement().unwrap(), @"ROLLBACK");
+
+        let count = Character::count(
+            &mut manager,
+            vec![CharacterSelector {
+                id: Field::Omit,
+                name: Field::Omit,
+                is_handsome: Field::Omit,
+                father_name: Field::Omit,
+            }],
+        )
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(count, @"3");
+    }
+
+    #[tokio::test]
+    async fn explicit_rollback() {
+        let mut manager = setup().await;
+
+        let mut transaction = (&mut manager).begin().await.unwrap();
+        Character::get(transaction.manager(), 1)
+            .await
+            .unwrap()
+            .remove(transaction.manager())
+            .await
+            .unwrap();
+        assert_snapshot!(transaction.last_statement().unwrap(), @r###"DELETE FROM "character" WHERE "id" = ?"###);
+        transaction.rollback().await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @"ROLLBACK");
+
+        let character = Character::get(&mut manager, 1).await;
+        assert!(character.is_ok());
+    }
+
+    #[tokio::test]
+    async fn nested() {
+        let mut manager = setup().await;
+
+        (&mut manager)
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    Character::get(transaction.manager(), 0)
+                        .await?
+                        .remove(transaction.manager())
+                        .await?;
+
+                    let nested = transaction
+                        .transaction(|nested| {
+                            Box::pin(async move {
+                                Character::get(nested.manager(), 1)
+                                    .await?
+                                    .remove(nested.manager())
+                                    .await?;
+                                Character::get(nested.manager(), 1).await?;
+                                Ok::<_, Box<dyn Error + Send + Sync>>(())
+                            })
+                        })
+                        .await;
+                    assert!(nested.is_err());
+                    assert_snapshot!(transaction.last_statement().unwrap(), @"ROLLBACK TO SAVEPOINT _sqlx_savepoint_1");
+
+                    Ok::<_, Box<dyn Error + Send + Sync>>(())
+                })
+            })
+            .await
+            .unwrap();
+
+        let characters = Character::find(
+            &mut manager,
+            vec![CharacterSelector {
+                id: Field::Omit,
+                name: Field::Omit,
+                is_handsome: Field::Omit,
+                father_name: Field::Omit,
+            }],
+        )
+        .all()
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(characters, @r###"
+        [
+            Character {
+                id: 1,
+                name: "Himmly",
+                is_handsome: false,
+                father_name: Some(
+                    "Gloyne",
+                ),
+            },
+            Character {
+                id: 2,
+                name: "Aragorn",
+                is_handsome: true,
+                father_name: Some(
+                    "Arathorn",
+                ),
+            },
+        ]
+        "###);
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod bulk {
+    use super::*;
+    use crate::entity::CopyRow;
+    use sqlx::{
+        encode::IsNull,
+        postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo},
+        Encode, Postgres, Type,
+    };
+
+    enum Race {
+        Elf,
+        Dwarf,
+    }
+
+    impl Type<Postgres> for Race {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("race")
+        }
+    }
+
+    impl PgHasArrayType for Race {
+        fn array_type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("_race")
+        }
+    }
+
+    impl Encode<'_, Postgres> for Race {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+            let label = match self {
+                Self::Elf => "elf",
+                Self::Dwarf => "dwarf",
+            };
+
+            <&str as Encode<Postgres>>::encode(label, buf)
+        }
+    }
+
+    #[test]
+    fn custom_enum() {
+        let mut buf = Vec::new();
+        CopyRow(&mut buf).field(&Race::Dwarf).unwrap();
+
+        assert_eq!(buf, b"\0\0\0\x05dwarf");
+    }
+
+    #[test]
+    fn custom_enum_array() {
+        let mut buf = Vec::new();
+        let result = CopyRow(&mut buf).field(&vec![Race::Elf, Race::Dwarf]);
+
+        assert_snapshot!(result.unwrap_err(), @"type _race must be resolved by the database and can't be copied");
+        assert!(buf.is_empty());
     }
 }
@@ -10,4 +10,6 @@ pub use manager::Manager;
 
 pub mod entity;
 pub mod manager;
-pub use foil_macros::{patch, patch_opt, selector, Create, Delete, Entity, Update, Value};
+pub use foil_macros::{
+    patch, patch_opt, selector, Create, Delete, Entity, FromRecord, Update, Value,
+};
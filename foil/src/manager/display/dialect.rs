@@ -0,0 +1,484 @@
+use std::fmt::{Result, Write};
+
+pub trait Dialect {
+    fn write_ident(&self, w: &mut dyn Write, ident: &str) -> Result {
+        write!(w, "\"{ident}\"")
+    }
+
+    fn parameter(&self, index: usize) -> String;
+
+    fn max_parameters(&self) -> usize {
+        65535
+    }
+
+    fn write_top(&self, _w: &mut dyn Write, _limit: Option<u32>, _offset: Option<u32>) -> Result {
+        Ok(())
+    }
+
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        _ordered: bool,
+    ) -> Result {
+        if let Some(limit) = limit {
+            write!(w, " LIMIT {limit}")?;
+        }
+
+        if let Some(offset) = offset {
+            write!(w, " OFFSET {offset}")?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_returning(&self) -> bool;
+
+    fn output_clause(&self) -> bool {
+        false
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax;
+
+    fn native_ilike(&self) -> bool;
+
+    fn explicit_like_escape(&self) -> bool;
+
+    fn native_nulls_order(&self) -> bool;
+
+    fn native_row_values(&self) -> bool {
+        true
+    }
+
+    fn boolean_expressions(&self) -> bool {
+        true
+    }
+
+    fn native_unnest(&self) -> bool {
+        false
+    }
+}
+
+pub trait WithDialect {
+    type Dialect: Dialect + Send + Sync + 'static;
+
+    fn dialect() -> Self::Dialect;
+}
+
+#[derive(Clone, Copy)]
+pub enum UpsertSyntax {
+    OnConflict { named_constraints: bool },
+    OnDuplicateKeyUpdate,
+    Merge,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostgresDialect;
+
+#[cfg(feature = "postgres")]
+impl Dialect for PostgresDialect {
+    fn parameter(&self, index: usize) -> String {
+        format!("${index}")
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnConflict {
+            named_constraints: true,
+        }
+    }
+
+    fn native_ilike(&self) -> bool {
+        true
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        false
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        true
+    }
+
+    fn native_unnest(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl WithDialect for sqlx::Postgres {
+    type Dialect = PostgresDialect;
+
+    fn dialect() -> Self::Dialect {
+        PostgresDialect
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MySqlDialect;
+
+#[cfg(feature = "mysql")]
+impl Dialect for MySqlDialect {
+    fn write_ident(&self, w: &mut dyn Write, ident: &str) -> Result {
+        write!(w, "`{ident}`")
+    }
+
+    fn parameter(&self, _index: usize) -> String {
+        "?".into()
+    }
+
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        _ordered: bool,
+    ) -> Result {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => write!(w, " LIMIT {limit} OFFSET {offset}"),
+            (Some(limit), None) => write!(w, " LIMIT {limit}"),
+            (None, Some(offset)) => write!(w, " LIMIT {} OFFSET {}", u64::MAX, offset),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnDuplicateKeyUpdate
+    }
+
+    fn native_ilike(&self) -> bool {
+        false
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        false
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl WithDialect for sqlx::MySql {
+    type Dialect = MySqlDialect;
+
+    fn dialect() -> Self::Dialect {
+        MySqlDialect
+    }
+}
+
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SqliteDialect;
+
+#[cfg(feature = "sqlite")]
+impl Dialect for SqliteDialect {
+    fn parameter(&self, _index: usize) -> String {
+        "?".into()
+    }
+
+    fn max_parameters(&self) -> usize {
+        32766
+    }
+
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        _ordered: bool,
+    ) -> Result {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => write!(w, " LIMIT {limit} OFFSET {offset}"),
+            (Some(limit), None) => write!(w, " LIMIT {limit}"),
+            (None, Some(offset)) => write!(w, " LIMIT -1 OFFSET {offset}"),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnConflict {
+            named_constraints: false,
+        }
+    }
+
+    fn native_ilike(&self) -> bool {
+        false
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        true
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl WithDialect for sqlx::Sqlite {
+    type Dialect = SqliteDialect;
+
+    fn dialect() -> Self::Dialect {
+        SqliteDialect
+    }
+}
+
+#[cfg(feature = "any")]
+#[derive(Clone, Copy, Debug, Default)]
+pub enum AnyDialect {
+    #[default]
+    Generic,
+    #[cfg(feature = "postgres")]
+    Postgres,
+    #[cfg(feature = "mysql")]
+    MySql,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+#[cfg(feature = "any")]
+impl AnyDialect {
+    #[must_use]
+    pub fn from_backend_name(name: &str) -> Self {
+        match name {
+            #[cfg(feature = "postgres")]
+            "PostgreSQL" => Self::Postgres,
+            #[cfg(feature = "mysql")]
+            "MySQL" => Self::MySql,
+            #[cfg(feature = "sqlite")]
+            "SQLite" => Self::Sqlite,
+            _ => Self::Generic,
+        }
+    }
+
+    #[must_use]
+    pub fn from_scheme(scheme: &str) -> Self {
+        match scheme {
+            #[cfg(feature = "postgres")]
+            "postgres" | "postgresql" => Self::Postgres,
+            #[cfg(feature = "mysql")]
+            "mysql" | "mariadb" => Self::MySql,
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Self::Sqlite,
+            _ => Self::Generic,
+        }
+    }
+
+    fn inner(self) -> &'static dyn Dialect {
+        match self {
+            Self::Generic => &GenericDialect,
+            #[cfg(feature = "postgres")]
+            Self::Postgres => &PostgresDialect,
+            #[cfg(feature = "mysql")]
+            Self::MySql => &MySqlDialect,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite => &SqliteDialect,
+        }
+    }
+}
+
+#[cfg(feature = "any")]
+impl Dialect for AnyDialect {
+    fn write_ident(&self, w: &mut dyn Write, ident: &str) -> Result {
+        self.inner().write_ident(w, ident)
+    }
+
+    fn parameter(&self, index: usize) -> String {
+        self.inner().parameter(index)
+    }
+
+    fn max_parameters(&self) -> usize {
+        self.inner().max_parameters()
+    }
+
+    fn write_top(&self, w: &mut dyn Write, limit: Option<u32>, offset: Option<u32>) -> Result {
+        self.inner().write_top(w, limit, offset)
+    }
+
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        ordered: bool,
+    ) -> Result {
+        self.inner().write_limit_offset(w, limit, offset, ordered)
+    }
+
+    fn supports_returning(&self) -> bool {
+        self.inner().supports_returning()
+    }
+
+    fn output_clause(&self) -> bool {
+        self.inner().output_clause()
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        self.inner().upsert_syntax()
+    }
+
+    fn native_ilike(&self) -> bool {
+        self.inner().native_ilike()
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        self.inner().explicit_like_escape()
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        self.inner().native_nulls_order()
+    }
+
+    fn native_row_values(&self) -> bool {
+        self.inner().native_row_values()
+    }
+
+    fn boolean_expressions(&self) -> bool {
+        self.inner().boolean_expressions()
+    }
+
+    fn native_unnest(&self) -> bool {
+        self.inner().native_unnest()
+    }
+}
+
+#[cfg(feature = "any")]
+impl WithDialect for sqlx::Any {
+    type Dialect = AnyDialect;
+
+    fn dialect() -> Self::Dialect {
+        AnyDialect::Generic
+    }
+}
+
+#[cfg(feature = "any")]
+struct GenericDialect;
+
+#[cfg(feature = "any")]
+impl Dialect for GenericDialect {
+    fn parameter(&self, _index: usize) -> String {
+        "?".into()
+    }
+
+    fn max_parameters(&self) -> usize {
+        32766
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnConflict {
+            named_constraints: true,
+        }
+    }
+
+    fn native_ilike(&self) -> bool {
+        false
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        false
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "mssql-dialect")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MssqlDialect;
+
+#[cfg(feature = "mssql-dialect")]
+impl Dialect for MssqlDialect {
+    fn write_ident(&self, w: &mut dyn Write, ident: &str) -> Result {
+        write!(w, "[{ident}]")
+    }
+
+    fn parameter(&self, index: usize) -> String {
+        format!("@P{index}")
+    }
+
+    fn max_parameters(&self) -> usize {
+        2100
+    }
+
+    fn write_top(&self, w: &mut dyn Write, limit: Option<u32>, offset: Option<u32>) -> Result {
+        match (limit, offset) {
+            (Some(limit), None) => write!(w, "TOP ({limit}) "),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        ordered: bool,
+    ) -> Result {
+        if let Some(offset) = offset {
+            if !ordered {
+                write!(w, " ORDER BY (SELECT NULL)")?;
+            }
+
+            write!(w, " OFFSET {offset} ROWS")?;
+
+            if let Some(limit) = limit {
+                write!(w, " FETCH NEXT {limit} ROWS ONLY")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn output_clause(&self) -> bool {
+        true
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::Merge
+    }
+
+    fn native_ilike(&self) -> bool {
+        false
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        true
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        false
+    }
+
+    fn native_row_values(&self) -> bool {
+        false
+    }
+
+    fn boolean_expressions(&self) -> bool {
+        false
+    }
+}
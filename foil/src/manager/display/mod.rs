@@ -1,145 +1,406 @@
 use super::{
-    CountQuery, DeleteQuery, FindOperator, InsertQuery, InsertReturningQuery, Order, SelectQuery,
-    Selector, UpdateQuery, Value,
+    AggregateQuery, ConflictAction, ConflictTarget, CountQuery, DeleteQuery, DeleteReturningQuery,
+    Filter, FindOperator, InputRecord, InsertQuery, InsertReturningQuery, Nulls, Order, OrderByCol,
+    RowOperator, SelectQuery, UnnestInsertQuery, UpdateQuery, UpdateReturningQuery, UpsertQuery,
+    Value,
 };
 use sqlx::Database;
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{Display, Formatter, Result, Write};
+use thiserror::Error;
 
-#[cfg(all(test, feature = "postgres", feature = "sqlite", feature = "mssql"))]
+mod dialect;
+
+pub use dialect::{Dialect, UpsertSyntax, WithDialect};
+
+#[cfg(feature = "any")]
+pub use dialect::AnyDialect;
+#[cfg(feature = "mssql-dialect")]
+pub use dialect::MssqlDialect;
+#[cfg(feature = "mysql")]
+pub use dialect::MySqlDialect;
+#[cfg(feature = "postgres")]
+pub use dialect::PostgresDialect;
+#[cfg(feature = "sqlite")]
+pub use dialect::SqliteDialect;
+
+#[cfg(all(
+    test,
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "any"
+))]
 mod test;
 
-impl<DB: Database + WithBindParameters> Display for SelectQuery<'_, DB> {
+pub trait ToSql {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, w: &mut W) -> Result;
+
+    fn validate<D: Dialect + ?Sized>(&self, _dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        Ok(())
+    }
+
+    fn display<'a, D: Dialect + ?Sized>(&'a self, dialect: &'a D) -> Sql<'a, Self, D> {
+        Sql {
+            query: self,
+            dialect,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct InvalidQuery(pub &'static str);
+
+pub struct Sql<'a, Q: ?Sized, D: ?Sized> {
+    query: &'a Q,
+    dialect: &'a D,
+}
+
+impl<Q: ToSql + ?Sized, D: Dialect + ?Sized> Display for Sql<'_, Q, D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.query.write_sql(self.dialect, f)
+    }
+}
+
+macro_rules! impl_display {
+    ( $( $query:ident ),* ) => {
+        $(
+            impl<DB: Database + WithDialect> Display for $query<'_, DB> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                    self.write_sql(&DB::dialect(), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(
+    SelectQuery,
+    CountQuery,
+    AggregateQuery,
+    InsertQuery,
+    InsertReturningQuery,
+    UnnestInsertQuery,
+    UpsertQuery,
+    UpdateQuery,
+    UpdateReturningQuery,
+    DeleteQuery,
+    DeleteReturningQuery
+);
+
+impl<DB: Database> ToSql for SelectQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
         write!(f, "SELECT ")?;
 
+        dialect.write_top(f, self.limit, self.offset)?;
+
         format_list(self.col_names.iter(), f, |col_name, f| {
-            write!(f, "\"{}\"", col_name)
+            write!(f, "{}", Ident(dialect, col_name))
         })?;
 
-        write!(f, " FROM \"{}\"", self.table_name)?;
+        write!(f, " FROM {}", Ident(dialect, self.table_name))?;
 
-        format_selectors(&self.selectors, &mut DB::parameter_factory(), f)?;
+        format_where(&self.filter, &mut Parameters::new(dialect), f)?;
 
         if let Some(order_by) = &self.order_by {
             write!(f, " ORDER BY ")?;
 
-            format_list(order_by.cols.iter(), f, |col_name, f| {
-                write!(f, "\"{}\"", col_name)
+            format_list(order_by.cols.iter(), f, |order_by_col, f| {
+                format_order_by_col(order_by_col, dialect, f)
             })?;
+        }
+
+        dialect.write_limit_offset(f, self.limit, self.offset, self.order_by.is_some())
+    }
+}
+
+impl<DB: Database> ToSql for CountQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        write!(
+            f,
+            "SELECT COUNT (*) AS {} FROM {}",
+            Ident(dialect, "cnt"),
+            Ident(dialect, self.table_name)
+        )?;
 
-            write!(f, " {}", order_by.order)?;
+        format_where(&self.filter, &mut Parameters::new(dialect), f)?;
+
+        Ok(())
+    }
+}
+
+impl<DB: Database> ToSql for AggregateQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        self.validate(dialect).map_err(|_| std::fmt::Error)?;
+
+        write!(f, "SELECT ")?;
+
+        for col_name in &self.group_by {
+            write!(f, "{}, ", Ident(dialect, col_name))?;
         }
 
-        if let Some(offset) = self.offset {
-            write!(f, " OFFSET {}", offset)?;
+        format_list(self.aggregates.iter(), f, |aggregate, f| {
+            write!(
+                f,
+                "{}({}) AS {}",
+                aggregate.function.as_str().to_uppercase(),
+                Ident(dialect, aggregate.col),
+                Ident(
+                    dialect,
+                    &format!("{}_{}", aggregate.function.as_str(), aggregate.col)
+                )
+            )
+        })?;
+
+        write!(f, " FROM {}", Ident(dialect, self.table_name))?;
+
+        format_where(&self.filter, &mut Parameters::new(dialect), f)?;
+
+        if !self.group_by.is_empty() {
+            write!(f, " GROUP BY ")?;
+
+            format_list(self.group_by.iter(), f, |col_name, f| {
+                write!(f, "{}", Ident(dialect, col_name))
+            })?;
         }
 
-        if let Some(limit) = self.limit {
-            write!(f, " LIMIT {}", limit)?;
+        Ok(())
+    }
+
+    fn validate<D: Dialect + ?Sized>(&self, _dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        if self.aggregates.is_empty() {
+            return Err(InvalidQuery("aggregate query has no aggregates"));
         }
 
         Ok(())
     }
 }
 
-impl<DB: Database + WithBindParameters> Display for CountQuery<'_, DB> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(
-            f,
-            "SELECT COUNT (*) AS \"cnt\" FROM \"{}\"",
-            self.table_name
-        )?;
+impl<DB: Database> ToSql for InsertQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        format_insert(self, &[], dialect, f)
+    }
+}
 
-        format_selectors(&self.selectors, &mut DB::parameter_factory(), f)?;
+impl<DB: Database> ToSql for InsertReturningQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        if !dialect.supports_returning() {
+            return self.insert_query.write_sql(dialect, f);
+        }
 
-        Ok(())
+        format_insert(&self.insert_query, self.returning_cols, dialect, f)
     }
 }
 
-impl<DB: Database + WithBindParameters> Display for InsertQuery<'_, DB> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "INSERT INTO \"{}\" (", self.table_name)?;
+impl<DB: Database> ToSql for UnnestInsertQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        self.validate(dialect).map_err(|_| std::fmt::Error)?;
 
-        let col_names = self
-            .values
-            .iter()
-            .flat_map(|input_record| input_record.cols().map(|(col_name, _)| col_name.to_owned()))
-            .fold(Vec::new(), |mut col_names, col_name| {
-                if !col_names.contains(&col_name) {
-                    col_names.push(col_name);
-                }
-                col_names
-            });
+        let InsertReturningQuery {
+            insert_query,
+            returning_cols,
+            ..
+        } = &self.insert_returning_query;
 
-        format_list(col_names.iter(), f, |col_name, f| {
-            write!(f, "\"{}\"", col_name)
+        write!(
+            f,
+            "INSERT INTO {} (",
+            Ident(dialect, insert_query.table_name)
+        )?;
+
+        format_list(self.columns.cols(), f, |(col_name, _), f| {
+            write!(f, "{}", Ident(dialect, col_name))
         })?;
 
-        write!(f, ") VALUES ")?;
+        write!(f, ")")?;
 
-        let mut parameter_factory = DB::parameter_factory();
+        format_output(returning_cols, "INSERTED", dialect, f)?;
 
-        format_list(self.values.iter(), f, |values, f| {
-            write!(f, "(")?;
+        write!(f, " SELECT ")?;
 
-            format_list(col_names.iter(), f, |col_name, f| {
-                if values.has_col(col_name) {
-                    write!(f, "{}", parameter_factory.get())?;
-                } else {
-                    write!(f, "DEFAULT")?;
-                }
+        format_list(self.columns.cols(), f, |(col_name, _), f| {
+            write!(f, "{}", Ident(dialect, col_name))
+        })?;
 
-                Ok(())
-            })?;
+        write!(f, " FROM UNNEST(")?;
 
-            write!(f, ")")?;
+        let mut parameters = Parameters::new(dialect);
 
-            Ok(())
+        format_list(self.columns.cols(), f, |_, f| {
+            write!(f, "{}", parameters.next())
         })?;
 
+        write!(f, ") WITH ORDINALITY AS {} (", Ident(dialect, "unnest"))?;
+
+        for (col_name, _) in self.columns.cols() {
+            write!(f, "{}, ", Ident(dialect, col_name))?;
+        }
+
+        write!(
+            f,
+            "{}) ORDER BY {}",
+            Ident(dialect, "ordinality"),
+            Ident(dialect, "ordinality")
+        )?;
+
+        format_returning(returning_cols, dialect, f)
+    }
+
+    fn validate<D: Dialect + ?Sized>(&self, dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        if !dialect.native_unnest() {
+            return Err(InvalidQuery("dialect does not support UNNEST inserts"));
+        }
+
         Ok(())
     }
 }
 
-impl<DB: Database + WithBindParameters> Display for InsertReturningQuery<'_, DB> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", self.insert_query)?;
+impl<DB: Database> ToSql for UpsertQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        self.validate(dialect).map_err(|_| std::fmt::Error)?;
+
+        let upsert_syntax = dialect.upsert_syntax();
 
-        if self.returning_cols.is_empty() {
-            return Ok(());
+        if let UpsertSyntax::Merge = upsert_syntax {
+            return format_merge(self, dialect, f);
         }
 
-        write!(f, " RETURNING ")?;
+        self.insert_query.write_sql(dialect, f)?;
+
+        match upsert_syntax {
+            UpsertSyntax::OnConflict { .. } => {
+                write!(f, " ON CONFLICT")?;
+
+                match &self.on_conflict.target {
+                    ConflictTarget::Cols(cols) => {
+                        write!(f, " (")?;
+                        format_list(cols.iter(), f, |col_name, f| {
+                            write!(f, "{}", Ident(dialect, col_name))
+                        })?;
+                        write!(f, ")")?;
+                    }
+                    ConflictTarget::Constraint(constraint) => {
+                        write!(f, " ON CONSTRAINT {}", Ident(dialect, constraint))?;
+                    }
+                }
 
-        format_list(self.returning_cols.iter(), f, |col_name, f| {
-            write!(f, "\"{}\"", col_name)
-        })?;
+                match &self.on_conflict.action {
+                    ConflictAction::DoNothing => write!(f, " DO NOTHING"),
+                    ConflictAction::Update(cols) => {
+                        write!(f, " DO UPDATE SET ")?;
+                        format_list(cols.iter(), f, |col_name, f| {
+                            write!(
+                                f,
+                                "{} = EXCLUDED.{}",
+                                Ident(dialect, col_name),
+                                Ident(dialect, col_name)
+                            )
+                        })
+                    }
+                }
+            }
+            UpsertSyntax::OnDuplicateKeyUpdate => {
+                write!(f, " ON DUPLICATE KEY UPDATE ")?;
+
+                match &self.on_conflict.action {
+                    ConflictAction::DoNothing => {
+                        let col_names = insert_col_names(&self.insert_query.values);
+                        let col_name = Ident(dialect, col_names[0]);
+
+                        write!(f, "{col_name} = {col_name}")
+                    }
+                    ConflictAction::Update(cols) => format_list(cols.iter(), f, |col_name, f| {
+                        write!(
+                            f,
+                            "{} = VALUES({})",
+                            Ident(dialect, col_name),
+                            Ident(dialect, col_name)
+                        )
+                    }),
+                }
+            }
+            UpsertSyntax::Merge => unreachable!(),
+        }
+    }
 
-        Ok(())
+    fn validate<D: Dialect + ?Sized>(&self, dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        match dialect.upsert_syntax() {
+            UpsertSyntax::OnConflict { named_constraints } => match &self.on_conflict.target {
+                ConflictTarget::Cols(cols) if cols.is_empty() => {
+                    Err(InvalidQuery("upsert conflict target has no columns"))
+                }
+                ConflictTarget::Constraint(_) if !named_constraints => Err(InvalidQuery(
+                    "dialect does not support named constraints as conflict targets",
+                )),
+                _ => Ok(()),
+            },
+            UpsertSyntax::OnDuplicateKeyUpdate => match &self.on_conflict.action {
+                ConflictAction::DoNothing
+                    if insert_col_names(&self.insert_query.values).is_empty() =>
+                {
+                    Err(InvalidQuery("upsert has no columns to insert"))
+                }
+                _ => Ok(()),
+            },
+            UpsertSyntax::Merge => match &self.on_conflict.target {
+                ConflictTarget::Cols(cols) if cols.is_empty() => {
+                    Err(InvalidQuery("upsert conflict target has no columns"))
+                }
+                ConflictTarget::Constraint(_) => Err(InvalidQuery(
+                    "dialect does not support named constraints as conflict targets",
+                )),
+                ConflictTarget::Cols(_)
+                    if insert_col_names(&self.insert_query.values).is_empty() =>
+                {
+                    Err(InvalidQuery("upsert has no columns to insert"))
+                }
+                ConflictTarget::Cols(_) => Ok(()),
+            },
+        }
     }
 }
 
-impl<DB: Database + WithBindParameters> Display for UpdateQuery<'_, DB> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "UPDATE \"{}\" SET ", self.table_name)?;
+impl<DB: Database> ToSql for UpdateQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        format_update(self, &[], dialect, f)
+    }
+}
 
-        let mut parameter_factory = DB::parameter_factory();
-        let cols = self.new_values.cols();
+impl<DB: Database> ToSql for UpdateReturningQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        self.validate(dialect).map_err(|_| std::fmt::Error)?;
 
-        format_list(cols, f, |(col_name, _), f| {
-            write!(f, "\"{}\" = {}", col_name, parameter_factory.get())
-        })?;
+        format_update(&self.update_query, self.returning_cols, dialect, f)
+    }
 
-        format_selectors(&self.selectors, &mut parameter_factory, f)?;
+    fn validate<D: Dialect + ?Sized>(&self, dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        if !self.returning_cols.is_empty() && !dialect.supports_returning() {
+            return Err(InvalidQuery("dialect does not support RETURNING"));
+        }
 
         Ok(())
     }
 }
 
-impl<DB: Database + WithBindParameters> Display for DeleteQuery<'_, DB> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "DELETE FROM \"{}\"", self.table_name)?;
-        format_selectors(&self.selectors, &mut DB::parameter_factory(), f)
+impl<DB: Database> ToSql for DeleteQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        format_delete(self, &[], dialect, f)
+    }
+}
+
+impl<DB: Database> ToSql for DeleteReturningQuery<'_, DB> {
+    fn write_sql<D: Dialect + ?Sized, W: Write>(&self, dialect: &D, f: &mut W) -> Result {
+        self.validate(dialect).map_err(|_| std::fmt::Error)?;
+
+        format_delete(&self.delete_query, self.returning_cols, dialect, f)
+    }
+
+    fn validate<D: Dialect + ?Sized>(&self, dialect: &D) -> std::result::Result<(), InvalidQuery> {
+        if !self.returning_cols.is_empty() && !dialect.supports_returning() {
+            return Err(InvalidQuery("dialect does not support RETURNING"));
+        }
+
+        Ok(())
     }
 }
 
@@ -152,28 +413,180 @@ impl Display for Order {
     }
 }
 
-fn format_selectors<DB: Database + WithBindParameters, W: std::fmt::Write>(
-    selectors: &[Selector<DB>],
-    parameter_factory: &mut DB::ParameterFactory,
+impl Display for Nulls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Self::First => "NULLS FIRST",
+            Self::Last => "NULLS LAST",
+        })
+    }
+}
+
+struct Ident<'a, D: ?Sized>(&'a D, &'a str);
+
+impl<D: Dialect + ?Sized> Display for Ident<'_, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.write_ident(f, self.1)
+    }
+}
+
+struct Parameters<'a, D: ?Sized> {
+    dialect: &'a D,
+    count: usize,
+    depth: usize,
+}
+
+impl<'a, D: Dialect + ?Sized> Parameters<'a, D> {
+    fn new(dialect: &'a D) -> Self {
+        Self {
+            dialect,
+            count: 0,
+            depth: 0,
+        }
+    }
+
+    fn next(&mut self) -> String {
+        self.count += 1;
+        self.dialect.parameter(self.count)
+    }
+}
+
+fn format_order_by_col<D: Dialect + ?Sized, W: Write>(
+    order_by_col: &OrderByCol<&str>,
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    let col = Ident(dialect, order_by_col.col);
+
+    match order_by_col.nulls {
+        Some(nulls) if dialect.native_nulls_order() => {
+            write!(f, "{} {} {}", col, order_by_col.order, nulls)
+        }
+        Some(nulls) => {
+            let nulls_order = match nulls {
+                Nulls::First => Order::Desc,
+                Nulls::Last => Order::Asc,
+            };
+
+            if dialect.boolean_expressions() {
+                write!(f, "{col} IS NULL {nulls_order}, ")?;
+            } else {
+                write!(
+                    f,
+                    "CASE WHEN {col} IS NULL THEN 1 ELSE 0 END {nulls_order}, "
+                )?;
+            }
+
+            write!(f, "{} {}", col, order_by_col.order)
+        }
+        None => write!(f, "{} {}", col, order_by_col.order),
+    }
+}
+
+fn format_where<DB: Database, D: Dialect + ?Sized, W: Write>(
+    filter: &Filter<DB>,
+    parameters: &mut Parameters<D>,
     w: &mut W,
 ) -> Result {
-    if selectors.len() == 1 && selectors[0].is_empty() {
+    if filter.is_all() {
         return Ok(());
     }
 
     write!(w, " WHERE ")?;
+    format_filter(filter, parameters, w)
+}
 
-    match selectors.len() {
-        0 => write!(w, "<empty list>")?,
-        1 => format_selector(selectors.first().unwrap(), parameter_factory, w)?,
+fn format_filter<DB: Database, D: Dialect + ?Sized, W: Write>(
+    filter: &Filter<DB>,
+    parameters: &mut Parameters<D>,
+    w: &mut W,
+) -> Result {
+    let dialect = parameters.dialect;
+
+    match filter {
+        Filter::Condition(col_name, op) => format_find_operator(col_name, op, parameters, w),
+        Filter::Row(col_names, op, values) if !dialect.native_row_values() => {
+            let values = values.iter().map(|_| parameters.next()).collect::<Vec<_>>();
+            format_expanded_row(col_names, *op, &values, dialect, w)
+        }
+        Filter::Row(col_names, op, values) => {
+            write!(w, "(")?;
+            format_list(col_names.iter(), w, |col_name, w| {
+                write!(w, "{}", Ident(dialect, col_name))
+            })?;
+            match op {
+                RowOperator::Lt => write!(w, ") < (")?,
+                RowOperator::Gt => write!(w, ") > (")?,
+            }
+            format_list(values.iter(), w, |_, w| write!(w, "{}", parameters.next()))?;
+            write!(w, ")")
+        }
+        Filter::InSubquery(col_name, subquery) => {
+            write!(
+                w,
+                "{} IN (SELECT {} FROM {}",
+                Ident(dialect, col_name),
+                Ident(dialect, subquery.col_name),
+                Ident(dialect, subquery.table_name)
+            )?;
+            format_where(&subquery.filter, parameters, w)?;
+            write!(w, ")")
+        }
+        Filter::Exists(exists) => {
+            let outer_alias = match parameters.depth {
+                0 => exists.outer_table_name.to_owned(),
+                depth => format!("t{depth}"),
+            };
+            parameters.depth += 1;
+            let alias = format!("t{}", parameters.depth);
+
+            write!(
+                w,
+                "EXISTS (SELECT 1 FROM {} AS {} WHERE {}.{} = {}.{}",
+                Ident(dialect, exists.table_name),
+                Ident(dialect, &alias),
+                Ident(dialect, &alias),
+                Ident(dialect, exists.col_name),
+                Ident(dialect, &outer_alias),
+                Ident(dialect, exists.outer_col_name)
+            )?;
+            if !exists.filter.is_all() {
+                write!(w, " AND (")?;
+                format_filter(&exists.filter, parameters, w)?;
+                write!(w, ")")?;
+            }
+
+            parameters.depth -= 1;
+            write!(w, ")")
+        }
+        Filter::And(filters) => format_junction(filters, " AND ", "1 = 1", parameters, w),
+        Filter::Or(filters) => format_junction(filters, " OR ", "1 = 0", parameters, w),
+        Filter::Not(filter) => {
+            write!(w, "NOT (")?;
+            format_filter(filter, parameters, w)?;
+            write!(w, ")")
+        }
+    }
+}
+
+fn format_junction<DB: Database, D: Dialect + ?Sized, W: Write>(
+    filters: &[Filter<DB>],
+    separator: &str,
+    empty: &str,
+    parameters: &mut Parameters<D>,
+    w: &mut W,
+) -> Result {
+    match filters.len() {
+        0 => write!(w, "{empty}")?,
+        1 => format_filter(filters.first().unwrap(), parameters, w)?,
         _ => {
-            for (index, selector) in selectors.iter().enumerate() {
+            for (index, filter) in filters.iter().enumerate() {
                 write!(w, "(")?;
-                format_selector(selector, parameter_factory, w)?;
+                format_filter(filter, parameters, w)?;
                 write!(w, ")")?;
 
-                if index != selectors.len() - 1 {
-                    write!(w, " OR ")?;
+                if index != filters.len() - 1 {
+                    write!(w, "{separator}")?;
                 }
             }
         }
@@ -182,198 +595,396 @@ fn format_selectors<DB: Database + WithBindParameters, W: std::fmt::Write>(
     Ok(())
 }
 
-fn format_selector<DB: Database + WithBindParameters, W: std::fmt::Write>(
-    selector: &Selector<DB>,
-    parameter_factory: &mut DB::ParameterFactory,
+fn format_find_operator<'q, DB: Database, D: Dialect + ?Sized, W: Write>(
+    col_name: &str,
+    op: &FindOperator<Box<dyn Value<'q, DB> + 'q>>,
+    parameters: &mut Parameters<D>,
     f: &mut W,
 ) -> Result {
-    let mut format_col =
-        |f: &mut W, (col_name, op): (&str, &FindOperator<Box<dyn Value<DB>>>)| match op {
-            FindOperator::Eq(value) => {
-                if value.is_null() {
-                    write!(f, "\"{}\" IS NULL", col_name)
-                } else {
-                    write!(f, "\"{}\" = {}", col_name, parameter_factory.get())
-                }
+    let dialect = parameters.dialect;
+    let col = Ident(dialect, col_name);
+
+    match op {
+        FindOperator::Eq(value) => {
+            if value.is_null() {
+                write!(f, "{col} IS NULL")
+            } else {
+                write!(f, "{} = {}", col, parameters.next())
+            }
+        }
+        FindOperator::Ne(value) => {
+            if value.is_null() {
+                write!(f, "{col} IS NOT NULL")
+            } else {
+                write!(f, "{} != {}", col, parameters.next())
+            }
+        }
+        FindOperator::In(values) if values.iter().all(|value| value.is_null()) => {
+            if values.is_empty() {
+                write!(f, "1 = 0")
+            } else {
+                write!(f, "{col} IS NULL")
+            }
+        }
+        FindOperator::NotIn(values) if values.iter().all(|value| value.is_null()) => {
+            if values.is_empty() {
+                write!(f, "1 = 1")
+            } else {
+                write!(f, "{col} IS NOT NULL")
             }
-            FindOperator::Ne(value) => {
+        }
+        FindOperator::In(values) => {
+            write!(f, "{col} IN (")?;
+
+            format_in_parameters(values, parameters, f)?;
+
+            write!(f, ")")?;
+            if values.iter().any(|value| value.is_null()) {
+                write!(f, " OR {col} IS NULL")?;
+            }
+
+            Ok(())
+        }
+        FindOperator::NotIn(values) => {
+            write!(f, "{col} NOT IN (")?;
+
+            format_in_parameters(values, parameters, f)?;
+
+            write!(f, ")")?;
+
+            if values.iter().any(|value| value.is_null()) {
+                write!(f, " AND {col} IS NOT NULL")?;
+            }
+
+            Ok(())
+        }
+        FindOperator::Lt(_) => write!(f, "{} < {}", col, parameters.next()),
+        FindOperator::Le(_) => write!(f, "{} <= {}", col, parameters.next()),
+        FindOperator::Gt(_) => write!(f, "{} > {}", col, parameters.next()),
+        FindOperator::Ge(_) => write!(f, "{} >= {}", col, parameters.next()),
+        FindOperator::Like(_) => {
+            write!(f, "{} LIKE {}", col, parameters.next())?;
+            format_like_escape(dialect, f)
+        }
+        FindOperator::NotLike(_) => {
+            write!(f, "{} NOT LIKE {}", col, parameters.next())?;
+            format_like_escape(dialect, f)
+        }
+        FindOperator::ILike(_) => {
+            if dialect.native_ilike() {
+                write!(f, "{} ILIKE {}", col, parameters.next())?;
+            } else {
+                write!(f, "LOWER({}) LIKE LOWER({})", col, parameters.next())?;
+            }
+            format_like_escape(dialect, f)
+        }
+        FindOperator::Between(_, _) => write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            col,
+            parameters.next(),
+            parameters.next()
+        ),
+    }
+}
+
+fn format_in_parameters<'q, DB: Database, D: Dialect + ?Sized, W: Write>(
+    values: &[Box<dyn Value<'q, DB> + 'q>],
+    parameters: &mut Parameters<D>,
+    f: &mut W,
+) -> Result {
+    format_list(
+        values
+            .iter()
+            .filter_map(|value| {
                 if value.is_null() {
-                    write!(f, "\"{}\" IS NOT NULL", col_name)
+                    None
                 } else {
-                    write!(f, "\"{}\" != {}", col_name, parameter_factory.get())
+                    Some(parameters.next())
                 }
-            }
-            FindOperator::In(values) => {
-                write!(f, "\"{}\" IN (", col_name)?;
-
-                format_list(
-                    values
-                        .iter()
-                        .filter_map(|value| {
-                            if value.is_null() {
-                                None
-                            } else {
-                                Some(parameter_factory.get())
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .iter(),
-                    f,
-                    |parameter, f| write!(f, "{}", parameter),
-                )?;
+            })
+            .collect::<Vec<_>>()
+            .iter(),
+        f,
+        |parameter, f| write!(f, "{parameter}"),
+    )
+}
 
-                write!(f, ")")?;
-                if values.iter().any(|value| value.is_null()) {
-                    write!(f, " OR \"{}\" IS NULL", col_name)?;
-                }
+fn format_like_escape<D: Dialect + ?Sized, W: Write>(dialect: &D, f: &mut W) -> Result {
+    if dialect.explicit_like_escape() {
+        write!(f, " ESCAPE '\\'")?;
+    }
 
-                Ok(())
+    Ok(())
+}
+
+fn insert_col_names<'q, DB: Database>(values: &[InputRecord<'q, DB>]) -> Vec<&'q str> {
+    values
+        .iter()
+        .flat_map(InputRecord::cols)
+        .map(|(col_name, _)| col_name)
+        .fold(Vec::new(), |mut col_names, col_name| {
+            if !col_names.contains(&col_name) {
+                col_names.push(col_name);
             }
-            FindOperator::NotIn(values) => {
-                write!(f, "\"{}\" NOT IN (", col_name)?;
-
-                format_list(
-                    values
-                        .iter()
-                        .filter_map(|value| {
-                            if value.is_null() {
-                                None
-                            } else {
-                                Some(parameter_factory.get())
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .iter(),
-                    f,
-                    |parameter, f| write!(f, "{}", parameter),
-                )?;
+            col_names
+        })
+}
 
-                write!(f, ")")?;
+fn format_insert<DB: Database, D: Dialect + ?Sized, W: Write>(
+    query: &InsertQuery<DB>,
+    returning_cols: &[&str],
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    write!(f, "INSERT INTO {} (", Ident(dialect, query.table_name))?;
 
-                if values.iter().any(|value| value.is_null()) {
-                    write!(f, " AND \"{}\" IS NOT NULL", col_name)?;
-                }
+    let col_names = insert_col_names(&query.values);
+
+    format_list(col_names.iter(), f, |col_name, f| {
+        write!(f, "{}", Ident(dialect, col_name))
+    })?;
+
+    write!(f, ")")?;
+
+    format_output(returning_cols, "INSERTED", dialect, f)?;
+
+    write!(f, " VALUES ")?;
+
+    format_values(&query.values, &col_names, &mut Parameters::new(dialect), f)?;
 
-                Ok(())
+    format_returning(returning_cols, dialect, f)
+}
+
+fn format_values<DB: Database, D: Dialect + ?Sized, W: Write>(
+    values: &[InputRecord<DB>],
+    col_names: &[&str],
+    parameters: &mut Parameters<D>,
+    f: &mut W,
+) -> Result {
+    format_list(values.iter(), f, |values, f| {
+        write!(f, "(")?;
+
+        format_list(col_names.iter(), f, |col_name, f| {
+            if values.has_col(col_name) {
+                write!(f, "{}", parameters.next())?;
+            } else {
+                write!(f, "DEFAULT")?;
             }
-        };
 
-    match selector.len() {
-        0 => write!(f, "<empty list>")?,
-        1 => format_col(f, selector.cols().next().unwrap())?,
-        _ => {
-            for (index, col) in selector.cols().enumerate() {
-                write!(f, "(")?;
-                format_col(f, col)?;
-                write!(f, ")")?;
+            Ok(())
+        })?;
 
-                if index != selector.cols().len() - 1 {
+        write!(f, ")")?;
+
+        Ok(())
+    })
+}
+
+fn format_merge<DB: Database, D: Dialect + ?Sized, W: Write>(
+    query: &UpsertQuery<DB>,
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    let target = Ident(dialect, "target");
+    let source = Ident(dialect, "source");
+    let col_names = insert_col_names(&query.insert_query.values);
+
+    write!(
+        f,
+        "MERGE INTO {} AS {} USING (VALUES ",
+        Ident(dialect, query.insert_query.table_name),
+        target
+    )?;
+
+    format_values(
+        &query.insert_query.values,
+        &col_names,
+        &mut Parameters::new(dialect),
+        f,
+    )?;
+
+    write!(f, ") AS {source} (")?;
+
+    format_list(col_names.iter(), f, |col_name, f| {
+        write!(f, "{}", Ident(dialect, col_name))
+    })?;
+
+    write!(f, ") ON ")?;
+
+    match &query.on_conflict.target {
+        ConflictTarget::Cols(cols) => {
+            for (index, col_name) in cols.iter().enumerate() {
+                if index != 0 {
                     write!(f, " AND ")?;
                 }
+
+                write!(
+                    f,
+                    "{}.{} = {}.{}",
+                    target,
+                    Ident(dialect, col_name),
+                    source,
+                    Ident(dialect, col_name)
+                )?;
             }
         }
+        ConflictTarget::Constraint(_) => return Err(std::fmt::Error),
     }
 
-    Ok(())
+    if let ConflictAction::Update(cols) = &query.on_conflict.action {
+        write!(f, " WHEN MATCHED THEN UPDATE SET ")?;
+
+        format_list(cols.iter(), f, |col_name, f| {
+            write!(
+                f,
+                "{}.{} = {}.{}",
+                target,
+                Ident(dialect, col_name),
+                source,
+                Ident(dialect, col_name)
+            )
+        })?;
+    }
+
+    write!(f, " WHEN NOT MATCHED THEN INSERT (")?;
+
+    format_list(col_names.iter(), f, |col_name, f| {
+        write!(f, "{}", Ident(dialect, col_name))
+    })?;
+
+    write!(f, ") VALUES (")?;
+
+    format_list(col_names.iter(), f, |col_name, f| {
+        write!(f, "{}.{}", source, Ident(dialect, col_name))
+    })?;
+
+    write!(f, ");")
 }
 
-fn format_list<T, W: std::fmt::Write>(
-    list: impl ExactSizeIterator<Item = T>,
+fn format_update<DB: Database, D: Dialect + ?Sized, W: Write>(
+    query: &UpdateQuery<DB>,
+    returning_cols: &[&str],
+    dialect: &D,
     f: &mut W,
-    mut format_fn: impl FnMut(T, &mut W) -> Result,
 ) -> Result {
-    let len = list.len();
+    write!(f, "UPDATE {} SET ", Ident(dialect, query.table_name))?;
 
-    if len == 0 {
-        write!(f, "<empty list>")?;
-        return Ok(());
-    }
+    let mut parameters = Parameters::new(dialect);
+    let cols = query.new_values.cols();
 
-    for (index, item) in list.enumerate() {
-        format_fn(item, f)?;
-        if index != len - 1 {
-            write!(f, ", ")?;
-        }
-    }
+    format_list(cols, f, |(col_name, _), f| {
+        write!(f, "{} = {}", Ident(dialect, col_name), parameters.next())
+    })?;
 
-    Ok(())
-}
+    format_output(returning_cols, "INSERTED", dialect, f)?;
 
-pub trait WithBindParameters {
-    type ParameterFactory: ParameterFactory;
+    format_where(&query.filter, &mut parameters, f)?;
 
-    fn parameter_factory() -> Self::ParameterFactory {
-        Self::ParameterFactory::default()
-    }
+    format_returning(returning_cols, dialect, f)
 }
 
-pub trait ParameterFactory: Default {
-    fn get(&mut self) -> String;
-}
+fn format_delete<DB: Database, D: Dialect + ?Sized, W: Write>(
+    query: &DeleteQuery<DB>,
+    returning_cols: &[&str],
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    write!(f, "DELETE FROM {}", Ident(dialect, query.table_name))?;
 
-#[cfg(feature = "postgres")]
-mod pg_parameters {
-    use super::{ParameterFactory, WithBindParameters};
+    format_output(returning_cols, "DELETED", dialect, f)?;
 
-    impl WithBindParameters for sqlx::Postgres {
-        type ParameterFactory = PgParameterFactory;
-    }
+    format_where(&query.filter, &mut Parameters::new(dialect), f)?;
 
-    #[derive(Default)]
-    pub struct PgParameterFactory(usize);
+    format_returning(returning_cols, dialect, f)
+}
 
-    impl ParameterFactory for PgParameterFactory {
-        fn get(&mut self) -> String {
-            self.0 += 1;
-            format!("${}", self.0)
+fn format_expanded_row<D: Dialect + ?Sized, W: Write>(
+    col_names: &[&str],
+    op: RowOperator,
+    values: &[String],
+    dialect: &D,
+    w: &mut W,
+) -> Result {
+    let op = match op {
+        RowOperator::Lt => "<",
+        RowOperator::Gt => ">",
+    };
+
+    for index in 0..col_names.len() {
+        if index != 0 {
+            write!(w, " OR ")?;
         }
-    }
-}
 
-#[cfg(any(feature = "mysql", feature = "sqlite", feature = "any"))]
-mod unordered_parameters {
-    use super::{ParameterFactory, WithBindParameters};
+        write!(w, "(")?;
 
-    #[cfg(feature = "mysql")]
-    impl WithBindParameters for sqlx::MySql {
-        type ParameterFactory = UnorderedParameterFactory;
-    }
+        for (col_name, value) in col_names.iter().zip(values).take(index) {
+            write!(w, "{} = {} AND ", Ident(dialect, col_name), value)?;
+        }
 
-    #[cfg(feature = "sqlite")]
-    impl WithBindParameters for sqlx::Sqlite {
-        type ParameterFactory = UnorderedParameterFactory;
+        write!(
+            w,
+            "{} {} {})",
+            Ident(dialect, col_names[index]),
+            op,
+            values[index]
+        )?;
     }
 
-    #[cfg(feature = "any")]
-    impl WithBindParameters for sqlx::Any {
-        type ParameterFactory = UnorderedParameterFactory;
+    Ok(())
+}
+
+fn format_output<D: Dialect + ?Sized, W: Write>(
+    returning_cols: &[&str],
+    prefix: &str,
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    if returning_cols.is_empty() || !dialect.output_clause() {
+        return Ok(());
     }
 
-    #[derive(Default)]
-    pub struct UnorderedParameterFactory;
+    write!(f, " OUTPUT ")?;
 
-    impl ParameterFactory for UnorderedParameterFactory {
-        fn get(&mut self) -> String {
-            "?".into()
-        }
+    format_list(returning_cols.iter(), f, |col_name, f| {
+        write!(f, "{}.{}", prefix, Ident(dialect, col_name))
+    })
+}
+
+fn format_returning<D: Dialect + ?Sized, W: Write>(
+    returning_cols: &[&str],
+    dialect: &D,
+    f: &mut W,
+) -> Result {
+    if returning_cols.is_empty() || dialect.output_clause() {
+        return Ok(());
     }
+
+    write!(f, " RETURNING ")?;
+
+    format_list(returning_cols.iter(), f, |col_name, f| {
+        write!(f, "{}", Ident(dialect, col_name))
+    })
 }
 
-#[cfg(feature = "mssql")]
-mod mssql_parameters {
-    use super::{ParameterFactory, WithBindParameters};
+fn format_list<T, W: Write>(
+    list: impl ExactSizeIterator<Item = T>,
+    f: &mut W,
+    mut format_fn: impl FnMut(T, &mut W) -> Result,
+) -> Result {
+    let len = list.len();
 
-    impl WithBindParameters for sqlx::Mssql {
-        type ParameterFactory = MssqlParameterFactory;
+    if len == 0 {
+        write!(f, "<empty list>")?;
+        return Ok(());
     }
 
-    #[derive(Default)]
-    pub struct MssqlParameterFactory(usize);
-
-    impl ParameterFactory for MssqlParameterFactory {
-        fn get(&mut self) -> String {
-            self.0 += 1;
-            format!("@P{}", self.0)
+    for (index, item) in list.enumerate() {
+        format_fn(item, f)?;
+        if index != len - 1 {
+            write!(f, ", ")?;
         }
     }
+
+    Ok(())
 }
@@ -1,10 +1,15 @@
 use crate::manager::{
-    display::{format_selectors, WithBindParameters},
-    CountQuery, DeleteQuery, FindOperator, InputRecord, InsertQuery, InsertReturningQuery, Order,
-    OrderBy, SelectQuery, Selector, UpdateQuery, Value,
+    display::{format_where, Parameters},
+    Aggregate, AggregateFunction, AggregateQuery, ConflictAction, ConflictTarget, CountQuery,
+    DeleteQuery, DeleteReturningQuery, Exists, Filter, FindOperator, InputRecord, InsertQuery,
+    InsertReturningQuery, Nulls, OnConflict, Order, OrderBy, OrderByCol, SelectQuery, Selector,
+    UnnestInsertQuery, UpdateQuery, UpdateReturningQuery, UpsertQuery, Value,
+};
+use crate::manager::{
+    AnyDialect, Dialect, MySqlDialect, PostgresDialect, SqliteDialect, ToSql, UpsertSyntax,
 };
 use insta::assert_snapshot;
-use sqlx::{Mssql, Postgres, Sqlite};
+use sqlx::{Any, MySql, Postgres, Sqlite};
 
 mod selectors {
     use super::*;
@@ -13,10 +18,14 @@ mod selectors {
     fn no_selectors() {
         let mut output = String::new();
 
-        format_selectors::<Postgres, _>(&[], &mut Postgres::parameter_factory(), &mut output)
-            .unwrap();
+        format_where::<Postgres, _, _>(
+            &Filter::Or(vec![]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
-        assert_snapshot!(output, @" WHERE <empty list>");
+        assert_snapshot!(output, @" WHERE 1 = 0");
     }
 
     #[test]
@@ -25,7 +34,12 @@ mod selectors {
 
         let cond = Selector::<Postgres>::default();
 
-        format_selectors(&[cond], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![cond]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @"");
     }
@@ -38,14 +52,14 @@ mod selectors {
         cond1.add_col("col1", FindOperator::Eq(Box::new(1)));
         let cond2 = Selector::<Postgres>::default();
 
-        format_selectors(
-            &[cond1, cond2],
-            &mut Postgres::parameter_factory(),
+        format_where(
+            &Filter::from_selectors(vec![cond1, cond2]),
+            &mut Parameters::new(&PostgresDialect),
             &mut output,
         )
         .unwrap();
 
-        assert_snapshot!(output, @r###" WHERE ("col1" = $1) OR (<empty list>)"###);
+        assert_snapshot!(output, @r###" WHERE ("col1" = $1) OR (1 = 1)"###);
     }
 
     #[test]
@@ -55,7 +69,12 @@ mod selectors {
         let mut selector = Selector::<Postgres>::default();
         selector.add_col("col", FindOperator::Eq(Box::new(1)));
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" = $1"###);
     }
@@ -67,7 +86,12 @@ mod selectors {
         let mut selector = Selector::<Postgres>::default();
         selector.add_col("col", FindOperator::Eq(Box::new(Option::<i32>::None)));
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" IS NULL"###);
     }
@@ -79,7 +103,12 @@ mod selectors {
         let mut selector = Selector::<Postgres>::default();
         selector.add_col("col", FindOperator::Ne(Box::new(1)));
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" != $1"###);
     }
@@ -91,7 +120,12 @@ mod selectors {
         let mut selector = Selector::<Postgres>::default();
         selector.add_col("col", FindOperator::Ne(Box::new(Option::<i32>::None)));
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" IS NOT NULL"###);
     }
@@ -106,7 +140,12 @@ mod selectors {
             FindOperator::In(vec![Box::new(1), Box::new(2), Box::new(3)]),
         );
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" IN ($1, $2, $3)"###);
     }
@@ -126,7 +165,12 @@ mod selectors {
             ]),
         );
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" IN ($1, $2) OR "col" IS NULL"###);
     }
@@ -141,7 +185,12 @@ mod selectors {
             FindOperator::NotIn(vec![Box::new(1), Box::new(2), Box::new(3)]),
         );
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" NOT IN ($1, $2, $3)"###);
     }
@@ -161,11 +210,169 @@ mod selectors {
             ]),
         );
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" NOT IN ($1, $2) AND "col" IS NOT NULL"###);
     }
 
+    #[test]
+    fn lt() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Lt(Box::new(1)));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" < $1"###);
+    }
+
+    #[test]
+    fn le() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Le(Box::new(1)));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" <= $1"###);
+    }
+
+    #[test]
+    fn gt() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Gt(Box::new(1)));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" > $1"###);
+    }
+
+    #[test]
+    fn ge() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Ge(Box::new(1)));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" >= $1"###);
+    }
+
+    #[test]
+    fn between() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Between(Box::new(1), Box::new(2)));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" BETWEEN $1 AND $2"###);
+    }
+
+    #[test]
+    fn like() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::Like(Box::new("abc%")));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" LIKE $1"###);
+    }
+
+    #[test]
+    fn not_like() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::NotLike(Box::new("abc%")));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" NOT LIKE $1"###);
+    }
+
+    #[test]
+    fn ilike() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col", FindOperator::ILike(Box::new("abc%")));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE "col" ILIKE $1"###);
+    }
+
+    #[test]
+    fn ilike_emulated() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Sqlite>::default();
+        selector.add_col("col", FindOperator::ILike(Box::new("abc%")));
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&SqliteDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @r###" WHERE LOWER("col") LIKE LOWER(?) ESCAPE '\'"###);
+    }
+
     #[test]
     fn multiple_cols() {
         let mut output = String::new();
@@ -174,7 +381,12 @@ mod selectors {
         selector.add_col("col1", FindOperator::Eq(Box::new(1)));
         selector.add_col("col2", FindOperator::Eq(Box::new(2)));
 
-        format_selectors(&[selector], &mut Postgres::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE ("col1" = $1) AND ("col2" = $2)"###);
     }
@@ -189,9 +401,9 @@ mod selectors {
         let mut cond2 = Selector::default();
         cond2.add_col("col2", FindOperator::Eq(Box::new(2)));
 
-        format_selectors(
-            &[cond1, cond2],
-            &mut Postgres::parameter_factory(),
+        format_where(
+            &Filter::from_selectors(vec![cond1, cond2]),
+            &mut Parameters::new(&PostgresDialect),
             &mut output,
         )
         .unwrap();
@@ -213,28 +425,38 @@ mod selectors {
             ]),
         );
 
-        format_selectors(&[selector], &mut Sqlite::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&SqliteDialect),
+            &mut output,
+        )
+        .unwrap();
 
         assert_snapshot!(output, @r###" WHERE "col" IN (?, ?, ?)"###);
     }
 
     #[test]
-    fn mssql_params() {
+    fn mysql_quoting() {
         let mut output = String::new();
 
-        let mut selector = Selector::<Mssql>::default();
+        let mut selector = Selector::<MySql>::default();
         selector.add_col(
             "col",
             FindOperator::In(vec![
-                Box::new(1) as Box<dyn Value<Mssql>>,
+                Box::new(1) as Box<dyn Value<MySql>>,
                 Box::new(2),
                 Box::new(3),
             ]),
         );
 
-        format_selectors(&[selector], &mut Mssql::parameter_factory(), &mut output).unwrap();
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&MySqlDialect),
+            &mut output,
+        )
+        .unwrap();
 
-        assert_snapshot!(output, @r###" WHERE "col" IN (@P1, @P2, @P3)"###);
+        assert_snapshot!(output, @" WHERE `col` IN (?, ?, ?)");
     }
 
     #[test]
@@ -257,9 +479,9 @@ mod selectors {
             FindOperator::NotIn(vec![Box::new(2), Box::new(Option::<i32>::None)]),
         );
 
-        format_selectors(
-            &[cond1, cond2],
-            &mut Postgres::parameter_factory(),
+        format_where(
+            &Filter::from_selectors(vec![cond1, cond2]),
+            &mut Parameters::new(&PostgresDialect),
             &mut output,
         )
         .unwrap();
@@ -268,6 +490,95 @@ mod selectors {
     }
 }
 
+mod filters {
+    use super::*;
+
+    #[test]
+    fn and_or() {
+        let mut output = String::new();
+
+        let filter = Filter::<Postgres>::And(vec![
+            Filter::condition("col1", FindOperator::Eq(1)),
+            Filter::Or(vec![
+                Filter::condition("col2", FindOperator::Eq(2)),
+                Filter::condition("col3", FindOperator::Gt(3)),
+            ]),
+        ]);
+
+        format_where(&filter, &mut Parameters::new(&PostgresDialect), &mut output).unwrap();
+
+        assert_snapshot!(output, @r###" WHERE ("col1" = $1) AND (("col2" = $2) OR ("col3" > $3))"###);
+    }
+
+    #[test]
+    fn not() {
+        let mut output = String::new();
+
+        let filter = Filter::<Postgres>::And(vec![
+            Filter::Not(Box::new(Filter::Or(vec![
+                Filter::condition("col1", FindOperator::Eq(1)),
+                Filter::condition("col2", FindOperator::Between(2, 3)),
+            ]))),
+            Filter::condition("col3", FindOperator::Ne(4)),
+        ]);
+
+        format_where(&filter, &mut Parameters::new(&PostgresDialect), &mut output).unwrap();
+
+        assert_snapshot!(output, @r###" WHERE (NOT (("col1" = $1) OR ("col2" BETWEEN $2 AND $3))) AND ("col3" != $4)"###);
+    }
+
+    #[test]
+    fn empty_junctions() {
+        let mut output = String::new();
+
+        let filter = Filter::<Postgres>::Or(vec![
+            Filter::And(vec![]),
+            Filter::Not(Box::new(Filter::Or(vec![]))),
+        ]);
+
+        format_where(&filter, &mut Parameters::new(&PostgresDialect), &mut output).unwrap();
+
+        assert_snapshot!(output, @r###" WHERE (1 = 1) OR (NOT (1 = 0))"###);
+    }
+
+    #[test]
+    fn all() {
+        let mut output = String::new();
+
+        format_where(
+            &Filter::<Postgres>::all(),
+            &mut Parameters::new(&PostgresDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @"");
+    }
+
+    #[test]
+    fn nested_exists() {
+        let mut output = String::new();
+
+        let filter = Filter::<Postgres>::Exists(Box::new(Exists {
+            table_name: "user",
+            col_name: "parent_id",
+            outer_table_name: "user",
+            outer_col_name: "id",
+            filter: Filter::Exists(Box::new(Exists {
+                table_name: "user",
+                col_name: "parent_id",
+                outer_table_name: "user",
+                outer_col_name: "id",
+                filter: Filter::condition("name", FindOperator::Eq("Aragorn")),
+            })),
+        }));
+
+        format_where(&filter, &mut Parameters::new(&PostgresDialect), &mut output).unwrap();
+
+        assert_snapshot!(output, @r###" WHERE EXISTS (SELECT 1 FROM "user" AS "t1" WHERE "t1"."parent_id" = "user"."id" AND (EXISTS (SELECT 1 FROM "user" AS "t2" WHERE "t2"."parent_id" = "t1"."id" AND ("name" = $1))))"###);
+    }
+}
+
 mod select_query {
     use super::*;
 
@@ -278,8 +589,8 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &["col1", "col2"],
-            selectors: vec![selector],
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![selector]),
             order_by: None,
             offset: None,
             limit: None,
@@ -295,8 +606,8 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &[],
-            selectors: vec![selector],
+            col_names: vec![].into(),
+            filter: Filter::from_selectors(vec![selector]),
             order_by: None,
             offset: None,
             limit: None,
@@ -312,17 +623,68 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &["col1", "col2"],
-            selectors: vec![selector],
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![selector]),
+            order_by: Some(OrderBy::new(Order::Asc, vec!["col1", "col2"])),
+            offset: None,
+            limit: None,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 ORDER BY "col1" ASC, "col2" ASC"###);
+    }
+
+    #[test]
+    fn order_by_per_col() {
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
+            order_by: Some(OrderBy {
+                cols: vec![
+                    OrderByCol {
+                        col: "col1",
+                        order: Order::Desc,
+                        nulls: Some(Nulls::Last),
+                    },
+                    OrderByCol {
+                        col: "col2",
+                        order: Order::Asc,
+                        nulls: None,
+                    },
+                ],
+            }),
+            offset: None,
+            limit: None,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" ORDER BY "col1" DESC NULLS LAST, "col2" ASC"###);
+    }
+
+    #[test]
+    fn order_by_nulls_emulated() {
+        let query = SelectQuery::<MySql> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
             order_by: Some(OrderBy {
-                order: Order::Asc,
-                cols: vec!["col1", "col2"],
+                cols: vec![
+                    OrderByCol {
+                        col: "col1",
+                        order: Order::Asc,
+                        nulls: Some(Nulls::First),
+                    },
+                    OrderByCol {
+                        col: "col2",
+                        order: Order::Desc,
+                        nulls: Some(Nulls::Last),
+                    },
+                ],
             }),
             offset: None,
             limit: None,
         };
 
-        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 ORDER BY "col1", "col2" ASC"###);
+        assert_snapshot!(query.to_string(), @"SELECT `col1`, `col2` FROM `table` ORDER BY `col1` IS NULL DESC, `col1` ASC, `col2` IS NULL ASC, `col2` DESC");
     }
 
     #[test]
@@ -332,14 +694,105 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &["col1", "col2"],
-            selectors: vec![selector],
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![selector]),
+            order_by: None,
+            offset: Some(3),
+            limit: None,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 OFFSET 3"###);
+    }
+
+    #[test]
+    fn skip_sqlite() {
+        let query = SelectQuery::<Sqlite> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
+            order_by: None,
+            offset: Some(3),
+            limit: None,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" LIMIT -1 OFFSET 3"###);
+    }
+
+    #[test]
+    fn skip_mysql() {
+        let query = SelectQuery::<MySql> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
             order_by: None,
             offset: Some(3),
             limit: None,
         };
 
-        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 SKIP 3"###);
+        assert_snapshot!(query.to_string(), @"SELECT `col1`, `col2` FROM `table` LIMIT 18446744073709551615 OFFSET 3");
+    }
+
+    #[test]
+    fn custom_dialect() {
+        struct UpperDialect;
+
+        impl Dialect for UpperDialect {
+            fn write_ident(&self, w: &mut dyn std::fmt::Write, ident: &str) -> std::fmt::Result {
+                write!(w, "{}", ident.to_uppercase())
+            }
+
+            fn parameter(&self, index: usize) -> String {
+                format!(":p{index}")
+            }
+
+            fn supports_returning(&self) -> bool {
+                false
+            }
+
+            fn upsert_syntax(&self) -> UpsertSyntax {
+                UpsertSyntax::OnDuplicateKeyUpdate
+            }
+
+            fn native_ilike(&self) -> bool {
+                false
+            }
+
+            fn explicit_like_escape(&self) -> bool {
+                false
+            }
+
+            fn native_nulls_order(&self) -> bool {
+                false
+            }
+        }
+
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::condition("col1", FindOperator::Eq(1)),
+            order_by: None,
+            offset: None,
+            limit: Some(3),
+        };
+
+        assert_snapshot!(query.display(&UpperDialect).to_string(), @"SELECT COL1, COL2 FROM TABLE WHERE COL1 = :p1 LIMIT 3");
+    }
+
+    #[test]
+    fn any_dialect() {
+        let query = SelectQuery::<Any> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::condition("col1", FindOperator::Like("a%")),
+            order_by: None,
+            offset: Some(2),
+            limit: None,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" LIKE ? OFFSET 2"###);
+        assert_snapshot!(query.display(&AnyDialect::from_scheme("postgres")).to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" LIKE $1 OFFSET 2"###);
+        assert_snapshot!(query.display(&AnyDialect::from_backend_name("MySQL")).to_string(), @"SELECT `col1`, `col2` FROM `table` WHERE `col1` LIKE ? LIMIT 18446744073709551615 OFFSET 2");
+        assert_snapshot!(query.display(&AnyDialect::from_backend_name("SQLite")).to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" LIKE ? ESCAPE '\' LIMIT -1 OFFSET 2"###);
     }
 
     #[test]
@@ -349,14 +802,14 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &["col1", "col2"],
-            selectors: vec![selector],
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![selector]),
             order_by: None,
             offset: None,
             limit: Some(3),
         };
 
-        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 TAKE 3"###);
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE "col1" = $1 LIMIT 3"###);
     }
 
     #[test]
@@ -379,17 +832,14 @@ mod select_query {
 
         let query = SelectQuery::<Postgres> {
             table_name: "table",
-            col_names: &["col1", "col2"],
-            selectors: vec![cond1, cond2],
-            order_by: Some(OrderBy {
-                order: Order::Asc,
-                cols: vec!["col1", "col2"],
-            }),
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![cond1, cond2]),
+            order_by: Some(OrderBy::new(Order::Asc, vec!["col1", "col2"])),
             offset: Some(3),
             limit: Some(5),
         };
 
-        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE (("col1" = $1) AND ("col2" IS NULL) AND ("col3" IN ($2) OR "col3" IS NULL)) OR (("col1" != $3) AND ("col2" IS NOT NULL) AND ("col3" NOT IN ($4) AND "col3" IS NOT NULL)) ORDER BY "col1", "col2" ASC SKIP 3 TAKE 5"###);
+        assert_snapshot!(query.to_string(), @r###"SELECT "col1", "col2" FROM "table" WHERE (("col1" = $1) AND ("col2" IS NULL) AND ("col3" IN ($2) OR "col3" IS NULL)) OR (("col1" != $3) AND ("col2" IS NOT NULL) AND ("col3" NOT IN ($4) AND "col3" IS NOT NULL)) ORDER BY "col1" ASC, "col2" ASC LIMIT 5 OFFSET 3"###);
     }
 }
 
@@ -403,32 +853,72 @@ mod count_query {
 
         let query = CountQuery::<Postgres> {
             table_name: "table",
-            selectors: vec![selector],
+            filter: Filter::from_selectors(vec![selector]),
         };
 
         assert_snapshot!(query.to_string(), @r###"SELECT COUNT (*) AS "cnt" FROM "table" WHERE "col1" = $1"###);
     }
 }
 
-mod insert_query {
+mod aggregate_query {
     use super::*;
 
     #[test]
     fn normal() {
-        let mut values = InputRecord::default();
-        values.add_col("col1", Box::new(1));
+        let mut selector = Selector::default();
+        selector.add_col("col1", FindOperator::Eq(Box::new(1)));
 
-        let query = InsertQuery::<Postgres> {
+        let query = AggregateQuery::<Postgres> {
             table_name: "table",
-            values: vec![values],
+            aggregates: vec![
+                Aggregate {
+                    function: AggregateFunction::Sum,
+                    col: "col2",
+                },
+                Aggregate {
+                    function: AggregateFunction::Avg,
+                    col: "col3",
+                },
+            ],
+            group_by: vec!["col4"],
+            filter: Filter::from_selectors(vec![selector]),
         };
 
-        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1") VALUES ($1)"###);
+        assert_snapshot!(query.to_string(), @r###"SELECT "col4", SUM("col2") AS "sum_col2", AVG("col3") AS "avg_col3" FROM "table" WHERE "col1" = $1 GROUP BY "col4""###);
     }
 
     #[test]
-    fn no_cols() {
-        let mut values = InputRecord::default();
+    fn no_aggregates() {
+        let query = AggregateQuery::<Postgres> {
+            table_name: "table",
+            aggregates: vec![],
+            group_by: vec!["col1"],
+            filter: Filter::all(),
+        };
+
+        assert_snapshot!(query.validate(&PostgresDialect).unwrap_err(), @"aggregate query has no aggregates");
+    }
+}
+
+mod insert_query {
+    use super::*;
+
+    #[test]
+    fn normal() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = InsertQuery::<Postgres> {
+            table_name: "table",
+            values: vec![values],
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1") VALUES ($1)"###);
+    }
+
+    #[test]
+    fn no_cols() {
+        let mut values = InputRecord::default();
         values.add_col("col1", Box::new(1));
 
         let query = InsertQuery::<Postgres> {
@@ -502,6 +992,7 @@ mod insert_returning_query {
                 values: vec![values],
             },
             returning_cols: &["col1", "col2"],
+            generated_key: None,
         };
 
         assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1") VALUES ($1) RETURNING "col1", "col2""###);
@@ -518,12 +1009,176 @@ mod insert_returning_query {
                 values: vec![values],
             },
             returning_cols: &[],
+            generated_key: None,
         };
 
         assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1") VALUES ($1)"###);
     }
 }
 
+mod unnest_insert_query {
+    use super::*;
+
+    #[test]
+    fn normal() {
+        let mut columns = InputRecord::default();
+        columns.add_col("col1", Box::new(vec![1, 2]));
+        columns.add_col("col2", Box::new(vec!["a", "b"]));
+
+        let query = UnnestInsertQuery::<Postgres> {
+            insert_returning_query: InsertReturningQuery {
+                insert_query: InsertQuery {
+                    table_name: "table",
+                    values: vec![],
+                },
+                returning_cols: &["id"],
+                generated_key: None,
+            },
+            columns,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1", "col2") SELECT "col1", "col2" FROM UNNEST($1, $2) WITH ORDINALITY AS "unnest" ("col1", "col2", "ordinality") ORDER BY "ordinality" RETURNING "id""###);
+    }
+
+    #[test]
+    fn no_returning() {
+        let mut columns = InputRecord::default();
+        columns.add_col("col1", Box::new(vec![1, 2]));
+
+        let query = UnnestInsertQuery::<Postgres> {
+            insert_returning_query: InsertReturningQuery {
+                insert_query: InsertQuery {
+                    table_name: "table",
+                    values: vec![],
+                },
+                returning_cols: &[],
+                generated_key: None,
+            },
+            columns,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1") SELECT "col1" FROM UNNEST($1) WITH ORDINALITY AS "unnest" ("col1", "ordinality") ORDER BY "ordinality""###);
+    }
+
+    #[test]
+    fn unsupported() {
+        let mut columns = InputRecord::default();
+        columns.add_col("col1", Box::new(vec![1, 2]));
+
+        let query = UnnestInsertQuery::<Sqlite> {
+            insert_returning_query: InsertReturningQuery {
+                insert_query: InsertQuery {
+                    table_name: "table",
+                    values: vec![],
+                },
+                returning_cols: &[],
+                generated_key: None,
+            },
+            columns,
+        };
+
+        assert_snapshot!(query.validate(&SqliteDialect).unwrap_err(), @"dialect does not support UNNEST inserts");
+    }
+}
+
+mod upsert_query {
+    use super::*;
+
+    #[test]
+    fn do_nothing() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+        values.add_col("col2", Box::new(2));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec!["col1"]),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1", "col2") VALUES ($1, $2) ON CONFLICT ("col1") DO NOTHING"###);
+    }
+
+    #[test]
+    fn update() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+        values.add_col("col2", Box::new(2));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Constraint("table_pkey"),
+                action: ConflictAction::Update(vec!["col2"]),
+            },
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "table" ("col1", "col2") VALUES ($1, $2) ON CONFLICT ON CONSTRAINT "table_pkey" DO UPDATE SET "col2" = EXCLUDED."col2""###);
+    }
+
+    #[test]
+    fn unsupported_constraint() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = UpsertQuery::<Sqlite> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Constraint("table_pkey"),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.validate(&SqliteDialect).unwrap_err(), @"dialect does not support named constraints as conflict targets");
+    }
+
+    #[test]
+    fn empty_conflict_cols() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec![]),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.validate(&PostgresDialect).unwrap_err(), @"upsert conflict target has no columns");
+    }
+
+    #[test]
+    fn mysql_do_nothing_without_cols() {
+        let query = UpsertQuery::<MySql> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![InputRecord::default()],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec![]),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.validate(&MySqlDialect).unwrap_err(), @"upsert has no columns to insert");
+    }
+}
+
 mod update_query {
     use super::*;
 
@@ -537,7 +1192,7 @@ mod update_query {
 
         let query = UpdateQuery::<Postgres> {
             table_name: "table",
-            selectors: vec![selectors],
+            filter: Filter::from_selectors(vec![selectors]),
             new_values: values,
         };
 
@@ -553,7 +1208,7 @@ mod update_query {
 
         let query = UpdateQuery::<Postgres> {
             table_name: "table",
-            selectors: vec![selectors],
+            filter: Filter::from_selectors(vec![selectors]),
             new_values: values,
         };
 
@@ -561,6 +1216,68 @@ mod update_query {
     }
 }
 
+mod update_returning_query {
+    use super::*;
+
+    #[test]
+    fn normal() {
+        let mut selectors = Selector::default();
+        selectors.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let mut values = InputRecord::default();
+        values.add_col("col2", Box::new(2));
+
+        let query = UpdateReturningQuery::<Postgres> {
+            update_query: UpdateQuery {
+                table_name: "table",
+                filter: Filter::from_selectors(vec![selectors]),
+                new_values: values,
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.to_string(), @r###"UPDATE "table" SET "col2" = $1 WHERE "col1" = $2 RETURNING "col1", "col2""###);
+    }
+
+    #[test]
+    fn unsupported() {
+        let mut values = InputRecord::default();
+        values.add_col("col2", Box::new(2));
+
+        let query = UpdateReturningQuery::<MySql> {
+            update_query: UpdateQuery {
+                table_name: "table",
+                filter: Filter::all(),
+                new_values: values,
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.validate(&MySqlDialect).unwrap_err(), @"dialect does not support RETURNING");
+        assert!(query.write_sql(&MySqlDialect, &mut String::new()).is_err());
+    }
+
+    #[test]
+    fn no_returning() {
+        let mut selectors = Selector::default();
+        selectors.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let mut values = InputRecord::default();
+        values.add_col("col2", Box::new(2));
+
+        let query = UpdateReturningQuery::<Postgres> {
+            update_query: UpdateQuery {
+                table_name: "table",
+                filter: Filter::from_selectors(vec![selectors]),
+                new_values: values,
+            },
+            returning_cols: &[],
+        };
+
+        assert_snapshot!(query.to_string(), @r###"UPDATE "table" SET "col2" = $1 WHERE "col1" = $2"###);
+    }
+}
+
 mod delete_query {
     use super::*;
 
@@ -571,9 +1288,289 @@ mod delete_query {
 
         let query = DeleteQuery::<Postgres> {
             table_name: "table",
-            selectors: vec![selectors],
+            filter: Filter::from_selectors(vec![selectors]),
         };
 
         assert_snapshot!(query.to_string(), @r###"DELETE FROM "table" WHERE "col1" = $1"###);
     }
 }
+
+mod delete_returning_query {
+    use super::*;
+
+    #[test]
+    fn normal() {
+        let mut selectors = Selector::default();
+        selectors.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let query = DeleteReturningQuery::<Postgres> {
+            delete_query: DeleteQuery {
+                table_name: "table",
+                filter: Filter::from_selectors(vec![selectors]),
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.to_string(), @r###"DELETE FROM "table" WHERE "col1" = $1 RETURNING "col1", "col2""###);
+    }
+
+    #[test]
+    fn unsupported() {
+        let query = DeleteReturningQuery::<MySql> {
+            delete_query: DeleteQuery {
+                table_name: "table",
+                filter: Filter::all(),
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.validate(&MySqlDialect).unwrap_err(), @"dialect does not support RETURNING");
+        assert!(query.write_sql(&MySqlDialect, &mut String::new()).is_err());
+    }
+}
+
+#[cfg(feature = "mssql-dialect")]
+mod mssql {
+    use super::*;
+    use crate::manager::{MssqlDialect, RowOperator};
+
+    #[test]
+    fn mssql_params() {
+        let mut output = String::new();
+
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col(
+            "col",
+            FindOperator::In(vec![
+                Box::new(1) as Box<dyn Value<Postgres>>,
+                Box::new(2),
+                Box::new(3),
+            ]),
+        );
+
+        format_where(
+            &Filter::from_selectors(vec![selector]),
+            &mut Parameters::new(&MssqlDialect),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_snapshot!(output, @" WHERE [col] IN (@P1, @P2, @P3)");
+    }
+
+    #[test]
+    fn select_top() {
+        let mut selector = Selector::default();
+        selector.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::from_selectors(vec![selector]),
+            order_by: None,
+            offset: None,
+            limit: Some(3),
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"SELECT TOP (3) [col1], [col2] FROM [table] WHERE [col1] = @P1");
+    }
+
+    #[test]
+    fn select_offset_fetch() {
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
+            order_by: Some(OrderBy::new(Order::Asc, vec!["col1"])),
+            offset: Some(2),
+            limit: Some(3),
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"SELECT [col1], [col2] FROM [table] ORDER BY [col1] ASC OFFSET 2 ROWS FETCH NEXT 3 ROWS ONLY");
+    }
+
+    #[test]
+    fn select_offset_unordered() {
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1", "col2"].into(),
+            filter: Filter::all(),
+            order_by: None,
+            offset: Some(2),
+            limit: None,
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"SELECT [col1], [col2] FROM [table] ORDER BY (SELECT NULL) OFFSET 2 ROWS");
+    }
+
+    #[test]
+    fn order_by_nulls() {
+        let query = SelectQuery::<Postgres> {
+            table_name: "table",
+            col_names: vec!["col1"].into(),
+            filter: Filter::all(),
+            order_by: Some(OrderBy {
+                cols: vec![OrderByCol {
+                    col: "col1",
+                    order: Order::Asc,
+                    nulls: Some(Nulls::First),
+                }],
+            }),
+            offset: None,
+            limit: None,
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"SELECT [col1] FROM [table] ORDER BY CASE WHEN [col1] IS NULL THEN 1 ELSE 0 END DESC, [col1] ASC");
+    }
+
+    #[test]
+    fn filters() {
+        let mut selector = Selector::<Postgres>::default();
+        selector.add_col("col1", FindOperator::ILike(Box::new("a%")));
+        selector.add_col("col2", FindOperator::Like(Box::new("b%")));
+
+        let query = CountQuery::<Postgres> {
+            table_name: "table",
+            filter: Filter::And(vec![
+                Filter::from_selectors(vec![selector]),
+                Filter::Row(
+                    vec!["col3", "col4", "col5"],
+                    RowOperator::Gt,
+                    vec![Box::new(1), Box::new(2), Box::new(3)],
+                ),
+            ]),
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @r###"SELECT COUNT (*) AS [cnt] FROM [table] WHERE ((LOWER([col1]) LIKE LOWER(@P1) ESCAPE '\') AND ([col2] LIKE @P2 ESCAPE '\')) AND (([col3] > @P3) OR ([col3] = @P3 AND [col4] > @P4) OR ([col3] = @P3 AND [col4] = @P4 AND [col5] > @P5))"###);
+    }
+
+    #[test]
+    fn insert_output() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = InsertReturningQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            returning_cols: &["col1", "col2"],
+            generated_key: None,
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"INSERT INTO [table] ([col1]) OUTPUT INSERTED.[col1], INSERTED.[col2] VALUES (@P1)");
+    }
+
+    #[test]
+    fn update_output() {
+        let mut selector = Selector::default();
+        selector.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let mut values = InputRecord::default();
+        values.add_col("col2", Box::new(2));
+
+        let query = UpdateReturningQuery::<Postgres> {
+            update_query: UpdateQuery {
+                table_name: "table",
+                filter: Filter::from_selectors(vec![selector]),
+                new_values: values,
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"UPDATE [table] SET [col2] = @P1 OUTPUT INSERTED.[col1], INSERTED.[col2] WHERE [col1] = @P2");
+    }
+
+    #[test]
+    fn delete_output() {
+        let mut selector = Selector::default();
+        selector.add_col("col1", FindOperator::Eq(Box::new(1)));
+
+        let query = DeleteReturningQuery::<Postgres> {
+            delete_query: DeleteQuery {
+                table_name: "table",
+                filter: Filter::from_selectors(vec![selector]),
+            },
+            returning_cols: &["col1", "col2"],
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"DELETE FROM [table] OUTPUT DELETED.[col1], DELETED.[col2] WHERE [col1] = @P1");
+    }
+
+    #[test]
+    fn merge_update() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+        values.add_col("col2", Box::new(2));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec!["col1"]),
+                action: ConflictAction::Update(vec!["col2"]),
+            },
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"MERGE INTO [table] AS [target] USING (VALUES (@P1, @P2)) AS [source] ([col1], [col2]) ON [target].[col1] = [source].[col1] WHEN MATCHED THEN UPDATE SET [target].[col2] = [source].[col2] WHEN NOT MATCHED THEN INSERT ([col1], [col2]) VALUES ([source].[col1], [source].[col2]);");
+    }
+
+    #[test]
+    fn merge_do_nothing() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec!["col1"]),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.display(&MssqlDialect).to_string(), @"MERGE INTO [table] AS [target] USING (VALUES (@P1)) AS [source] ([col1]) ON [target].[col1] = [source].[col1] WHEN NOT MATCHED THEN INSERT ([col1]) VALUES ([source].[col1]);");
+    }
+
+    #[test]
+    fn merge_constraint() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Constraint("table_pkey"),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.validate(&MssqlDialect).unwrap_err(), @"dialect does not support named constraints as conflict targets");
+    }
+
+    #[test]
+    fn merge_empty_conflict_cols() {
+        let mut values = InputRecord::default();
+        values.add_col("col1", Box::new(1));
+
+        let query = UpsertQuery::<Postgres> {
+            insert_query: InsertQuery {
+                table_name: "table",
+                values: vec![values],
+            },
+            on_conflict: OnConflict {
+                target: ConflictTarget::Cols(vec![]),
+                action: ConflictAction::DoNothing,
+            },
+        };
+
+        assert_snapshot!(query.validate(&MssqlDialect).unwrap_err(), @"upsert conflict target has no columns");
+    }
+}
@@ -1,15 +1,351 @@
+#[cfg(feature = "any")]
+use crate::manager::AnyDialect;
 use crate::{
-    manager::{CountQuery, DeleteQuery, FindOperator, InputRecord, Record, SelectQuery, Selector},
+    manager::{
+        Begin, Dialect, Filter, FindOperator, InputRecord, InsertQuery, InsertReturningQuery,
+        Record, SelectQuery, ToSql, Transaction, UpsertQuery, WithDialect,
+    },
     Manager,
 };
 use futures::stream::BoxStream;
-use sqlx::{database::HasArguments, Database, Decode, Executor, Row, Type};
+use sqlx::{database::HasArguments, query::Query, Acquire, Database, Decode, Executor, Row, Type};
+use std::{borrow::Cow, ops::DerefMut};
+
+pub struct DialectManager<E, D> {
+    executor: E,
+    dialect: D,
+    transactional_chunks: bool,
+}
+
+impl<E, D> DialectManager<E, D> {
+    pub fn new(executor: E, dialect: D) -> Self {
+        Self {
+            executor,
+            dialect,
+            transactional_chunks: true,
+        }
+    }
+
+    #[must_use]
+    pub fn transactional_chunks(mut self, transactional_chunks: bool) -> Self {
+        self.transactional_chunks = transactional_chunks;
+        self
+    }
+}
+
+pub trait ExecutorDialect<'m, DB: Database>: Executor<'m, Database = DB> + 'm {
+    type Dialect: Dialect + Send + Sync + 'm;
+
+    fn dialect(&self) -> Self::Dialect;
+}
+
+fn with_dialect<'m, DB: Database, T: ExecutorDialect<'m, DB>>(
+    executor: T,
+) -> DialectManager<T, T::Dialect> {
+    let dialect = executor.dialect();
+    DialectManager::new(executor, dialect)
+}
+
+macro_rules! impl_executor_dialect_for_db {
+    ($DB:path) => {
+        impl<'m, T> ExecutorDialect<'m, $DB> for T
+        where
+            T: Executor<'m, Database = $DB> + 'm,
+        {
+            type Dialect = <$DB as WithDialect>::Dialect;
+
+            fn dialect(&self) -> Self::Dialect {
+                <$DB as WithDialect>::dialect()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "mysql")]
+impl_executor_dialect_for_db!(sqlx::MySql);
+
+#[cfg(feature = "postgres")]
+impl_executor_dialect_for_db!(sqlx::Postgres);
+
+#[cfg(feature = "sqlite")]
+impl_executor_dialect_for_db!(sqlx::Sqlite);
+
+#[cfg(feature = "any")]
+impl<'m> ExecutorDialect<'m, sqlx::Any> for &'m mut sqlx::AnyConnection {
+    type Dialect = AnyDialect;
+
+    fn dialect(&self) -> Self::Dialect {
+        AnyDialect::from_backend_name(self.backend_name())
+    }
+}
+
+#[cfg(feature = "any")]
+impl<'m, 'p: 'm> ExecutorDialect<'m, sqlx::Any> for &'p sqlx::Pool<sqlx::Any> {
+    type Dialect = AnyDialect;
+
+    fn dialect(&self) -> Self::Dialect {
+        AnyDialect::from_scheme(self.connect_options().database_url.scheme())
+    }
+}
+
+macro_rules! execute_chunk {
+    ($executor:expr, $dialect:expr, $query:ident, $values:expr) => {{
+        let sql = to_sql(&$query, $dialect)?;
+        let sqlx_query = create_sqlx_query(&sql, Filter::all(), $values);
+
+        $executor.execute(sqlx_query).await?
+    }};
+}
+
+macro_rules! insert_returning_chunk {
+    ($executor:expr, $dialect:expr, $query:ident) => {{
+        let executor = $executor;
+        let dialect = $dialect;
+
+        async move {
+            let sql = to_sql(&$query, dialect)?;
+            let sqlx_query = create_sqlx_query(&sql, Filter::all(), $query.insert_query.values);
+
+            Ok::<_, sqlx::Error>(
+                executor
+                    .fetch_all(sqlx_query)
+                    .await?
+                    .into_iter()
+                    .map(Record::from_row)
+                    .collect::<Vec<_>>(),
+            )
+        }
+        .await
+    }};
+}
+
+macro_rules! insert_reselect_chunk {
+    ($conn:expr, $dialect:expr, $query:ident) => {{
+        let conn = $conn;
+        let dialect = $dialect;
+
+        async move {
+            let InsertReturningQuery {
+                insert_query,
+                returning_cols,
+                generated_key,
+            } = $query;
+            let InsertQuery { table_name, values } = insert_query;
+
+            if let Some(key) = generated_key {
+                if values.iter().any(|input_record| {
+                    input_record.has_col(key)
+                        && returning_cols.iter().any(|col| !input_record.has_col(col))
+                }) {
+                    return Err(sqlx::Error::Configuration(
+                        "cannot re-select generated columns of rows with an explicit key".into(),
+                    ));
+                }
+            }
+
+            let mut col_names = returning_cols.to_vec();
+            if let Some(key) = generated_key.filter(|key| !col_names.contains(key)) {
+                col_names.push(key);
+            }
+
+            let mut records = Vec::with_capacity(values.len());
+            for input_record in values {
+                let key = generated_key.filter(|key| !input_record.has_col(key));
+
+                let insert_query = InsertQuery {
+                    table_name,
+                    values: vec![input_record],
+                };
+                let sql = to_sql(&insert_query, dialect)?;
+                let sqlx_query = create_sqlx_query(&sql, Filter::all(), insert_query.values);
+                let result = (&mut *conn).execute(sqlx_query).await?;
+
+                let record = if let Some(key) = key {
+                    let id = result
+                        .first_generated_id()
+                        .and_then(|id| i64::try_from(id).ok())
+                        .ok_or(sqlx::Error::RowNotFound)?;
+
+                    let query = SelectQuery {
+                        table_name,
+                        col_names: Cow::Borrowed(&col_names),
+                        filter: Filter::condition(key, FindOperator::Eq(id)),
+                        order_by: None,
+                        offset: None,
+                        limit: None,
+                    };
+                    let sql = to_sql(&query, dialect)?;
+                    let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
+
+                    (&mut *conn)
+                        .fetch_optional(sqlx_query)
+                        .await?
+                        .map(Record::from_row)
+                        .ok_or(sqlx::Error::RowNotFound)?
+                } else {
+                    Record::new()
+                };
+
+                records.push(record);
+            }
+
+            Ok::<_, sqlx::Error>(records)
+        }
+        .await
+    }};
+}
 
 macro_rules! impl_manager_for_db_executor {
     ($DB:path) => {
         impl<'m, T> Manager<'m, $DB> for T
         where
-            T: Executor<'m, Database = $DB> + 'm,
+            T: ExecutorDialect<'m, $DB> + Acquire<'m, Database = $DB>,
+        {
+            type Error = sqlx::Error;
+
+            fn select<'q, 'o>(
+                self,
+                query: crate::manager::SelectQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).select(query)
+            }
+
+            fn count<'q, 'o>(
+                self,
+                query: crate::manager::CountQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<i64>>
+            where
+                'm: 'o,
+                'q: 'o,
+                for<'a> i64: Type<$DB> + Decode<'a, $DB>,
+                for<'a> &'a str: sqlx::ColumnIndex<<$DB as sqlx::Database>::Row>,
+            {
+                with_dialect(self).count(query)
+            }
+
+            fn aggregate<'q, 'o>(
+                self,
+                query: crate::manager::AggregateQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).aggregate(query)
+            }
+
+            fn insert<'q, 'o>(
+                self,
+                query: crate::manager::InsertQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<()>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).insert(query)
+            }
+
+            fn insert_returning<'q, 'o>(
+                self,
+                query: crate::manager::InsertReturningQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).insert_returning(query)
+            }
+
+            fn insert_unnest<'q, 'o>(
+                self,
+                query: crate::manager::UnnestInsertQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).insert_unnest(query)
+            }
+
+            fn upsert<'q, 'o>(
+                self,
+                query: crate::manager::UpsertQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).upsert(query)
+            }
+
+            fn update<'q, 'o>(
+                self,
+                query: crate::manager::UpdateQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).update(query)
+            }
+
+            fn update_returning<'q, 'o>(
+                self,
+                query: crate::manager::UpdateReturningQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).update_returning(query)
+            }
+
+            fn delete<'q, 'o>(
+                self,
+                query: crate::manager::DeleteQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).delete(query)
+            }
+
+            fn delete_returning<'q, 'o>(
+                self,
+                query: crate::manager::DeleteReturningQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).delete_returning(query)
+            }
+
+            fn query<'q, 'o, Q: sqlx::Execute<'q, $DB> + 'q>(
+                self,
+                query: Q,
+            ) -> BoxStream<'o, Result<Record<$DB>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                with_dialect(self).query(query)
+            }
+
+            fn visit_dialect<R>(&self, visit: impl FnOnce(&dyn Dialect) -> R) -> R {
+                visit(&ExecutorDialect::dialect(self))
+            }
+        }
+
+        impl<'m, T, D> Manager<'m, $DB> for DialectManager<T, D>
+        where
+            T: Executor<'m, Database = $DB> + Acquire<'m, Database = $DB> + 'm,
+            D: Dialect + Send + Sync + 'm,
         {
             type Error = sqlx::Error;
 
@@ -21,41 +357,42 @@ macro_rules! impl_manager_for_db_executor {
                 'm: 'o,
                 'q: 'o,
             {
+                let Self { executor, dialect, transactional_chunks } = self;
+
                 if query.col_names.is_empty() {
                     Box::pin(futures::stream::once(async { Ok(Record::new()) }))
-                } else if query.selectors.is_empty()
-                    || query.selectors.iter().any(|selector| {
-                        selector.cols().any(|(_, find_operator)| {
-                            if let FindOperator::In(vals) = find_operator {
-                                vals.is_empty()
-                            } else {
-                                false
-                            }
-                        })
-                    })
+                } else if query.filter.matches_nothing()
                 {
                     Box::pin(futures::stream::empty())
                 } else {
                     Box::pin(async_stream::try_stream! {
-                        let sql = if query.selectors.iter().any(Selector::is_empty) {
-                            SelectQuery::<$DB> {
-                                table_name: query.table_name,
-                                col_names: query.col_names,
-                                selectors: vec![Selector::new()],
-                                order_by: query.order_by,
-                                offset: query.offset,
-                                limit: query.limit
-                            }.to_string()
+                        let mut queries = chunk_select_query(query, dialect.max_parameters());
+
+                        if queries.len() == 1 {
+                            let query = queries.pop().unwrap();
+                            let sql = to_sql(&query, &dialect)?;
+                            let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
+
+                            for await result in executor.fetch(sqlx_query) {
+                                let row = result?;
+                                let record = Record::from_row(row);
+                                yield record
+                            }
                         } else {
-                            query.to_string()
-                        };
+                            let mut conn = ChunkConnection::open(executor, transactional_chunks).await?;
 
-                        let sqlx_query = create_sqlx_query(&sql, query.selectors, vec![]);
+                            for query in queries {
+                                let sql = to_sql(&query, &dialect)?;
+                                let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
 
-                        for await result in self.fetch(sqlx_query) {
-                            let row = result?;
-                            let record = Record::from_row(row);
-                            yield record
+                                for await result in conn.get().fetch(sqlx_query) {
+                                    let row = result?;
+                                    let record = Record::from_row(row);
+                                    yield record
+                                }
+                            }
+
+                            conn.finish().await?;
                         }
                     })
                 }
@@ -71,33 +408,17 @@ macro_rules! impl_manager_for_db_executor {
                 for<'a> i64: Type<$DB> + Decode<'a, $DB>,
                 for<'a> &'a str: sqlx::ColumnIndex<<$DB as sqlx::Database>::Row>,
             {
-                if query.selectors.is_empty()
-                    || query.selectors.iter().any(|selector| {
-                        selector.cols().any(|(_, find_operator)| {
-                            if let FindOperator::In(vals) = find_operator {
-                                vals.is_empty()
-                            } else {
-                                false
-                            }
-                        })
-                    })
+                let Self { executor, dialect, .. } = self;
+
+                if query.filter.matches_nothing()
                 {
                     Box::pin(async { Ok(0) })
                 } else {
-                    Box::pin(async {
-                        let sql = if query.selectors.iter().any(Selector::is_empty) {
-                            CountQuery::<$DB> {
-                                table_name: query.table_name,
-                                selectors: vec![Selector::new()],
-                            }
-                            .to_string()
-                        } else {
-                            query.to_string()
-                        };
+                    Box::pin(async move {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
 
-                        let sqlx_query = create_sqlx_query(&sql, query.selectors, vec![]);
-
-                        let row = self.fetch_one(sqlx_query).await?;
+                        let row = executor.fetch_one(sqlx_query).await?;
 
                         let count = row.try_get("cnt")?;
 
@@ -106,6 +427,33 @@ macro_rules! impl_manager_for_db_executor {
                 }
             }
 
+            fn aggregate<'q, 'o>(
+                self,
+                query: crate::manager::AggregateQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                let Self { executor, dialect, .. } = self;
+
+                if !query.group_by.is_empty() && query.filter.matches_nothing()
+                {
+                    Box::pin(futures::stream::empty())
+                } else {
+                    Box::pin(async_stream::try_stream! {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
+
+                        for await result in executor.fetch(sqlx_query) {
+                            let row = result?;
+                            let record = Record::from_row(row);
+                            yield record
+                        }
+                    })
+                }
+            }
+
             fn insert<'q, 'o>(
                 self,
                 query: crate::manager::InsertQuery<'q, $DB>,
@@ -114,6 +462,8 @@ macro_rules! impl_manager_for_db_executor {
                 'm: 'o,
                 'q: 'o,
             {
+                let Self { executor, dialect, transactional_chunks } = self;
+
                 if query.values.is_empty()
                     || query
                         .values
@@ -122,11 +472,23 @@ macro_rules! impl_manager_for_db_executor {
                 {
                     Box::pin(async { Ok(()) })
                 } else {
-                    Box::pin(async {
-                        let sql = query.to_string();
-                        let sqlx_query = create_sqlx_query(&sql, vec![], query.values);
+                    Box::pin(async move {
+                        let InsertQuery { table_name, values } = query;
+                        let mut chunks = chunk_input_records(values, dialect.max_parameters());
 
-                        self.execute(sqlx_query).await?;
+                        if chunks.len() == 1 {
+                            let query = InsertQuery { table_name, values: chunks.pop().unwrap() };
+                            execute_chunk!(executor, &dialect, query, query.values);
+                        } else {
+                            let mut conn = ChunkConnection::open(executor, transactional_chunks).await?;
+
+                            for values in chunks {
+                                let query = InsertQuery { table_name, values };
+                                execute_chunk!(conn.get(), &dialect, query, query.values);
+                            }
+
+                            conn.finish().await?;
+                        }
 
                         Ok(())
                     })
@@ -141,6 +503,8 @@ macro_rules! impl_manager_for_db_executor {
                 'm: 'o,
                 'q: 'o,
             {
+                let Self { executor, dialect, transactional_chunks } = self;
+
                 if query.insert_query.values.is_empty()
                     || query
                         .insert_query
@@ -151,10 +515,69 @@ macro_rules! impl_manager_for_db_executor {
                     Box::pin(futures::stream::empty())
                 } else {
                     Box::pin(async_stream::try_stream! {
-                        let sql = query.to_string();
-                        let sqlx_query = create_sqlx_query(&sql, vec![], query.insert_query.values);
+                        let InsertReturningQuery { insert_query, returning_cols, generated_key } = query;
+                        let InsertQuery { table_name, values } = insert_query;
+                        let mut chunks = chunk_input_records(values, dialect.max_parameters());
+
+                        if chunks.len() == 1 && dialect.supports_returning() {
+                            let query = InsertReturningQuery {
+                                insert_query: InsertQuery { table_name, values: chunks.pop().unwrap() },
+                                returning_cols,
+                                generated_key,
+                            };
+
+                            for record in insert_returning_chunk!(executor, &dialect, query)? {
+                                yield record;
+                            }
+                        } else {
+                            let mut conn = ChunkConnection::open(executor, transactional_chunks).await?;
 
-                        for await result in self.fetch(sqlx_query) {
+                            for values in chunks {
+                                let query = InsertReturningQuery {
+                                    insert_query: InsertQuery { table_name, values },
+                                    returning_cols,
+                                    generated_key,
+                                };
+
+                                let records = if dialect.supports_returning() {
+                                    insert_returning_chunk!(conn.get(), &dialect, query)?
+                                } else {
+                                    insert_reselect_chunk!(conn.get(), &dialect, query)?
+                                };
+
+                                for record in records {
+                                    yield record;
+                                }
+                            }
+
+                            conn.finish().await?;
+                        }
+                    })
+                }
+            }
+
+            fn insert_unnest<'q, 'o>(
+                self,
+                query: crate::manager::UnnestInsertQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                if !self.dialect.native_unnest() {
+                    return self.insert_returning(query.insert_returning_query);
+                }
+
+                let Self { executor, dialect, .. } = self;
+
+                if query.columns.is_empty() {
+                    Box::pin(futures::stream::empty())
+                } else {
+                    Box::pin(async_stream::try_stream! {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(&sql, Filter::all(), vec![query.columns]);
+
+                        for await result in executor.fetch(sqlx_query) {
                             let row = result?;
                             let record = Record::from_row(row);
                             yield record
@@ -163,36 +586,113 @@ macro_rules! impl_manager_for_db_executor {
                 }
             }
 
-            fn update<'q, 'o>(
+            fn upsert<'q, 'o>(
                 self,
-                query: crate::manager::UpdateQuery<'q, $DB>,
-            ) -> futures::future::BoxFuture<'o, sqlx::Result<()>>
+                query: crate::manager::UpsertQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
             where
                 'm: 'o,
                 'q: 'o,
             {
-                if query.selectors.is_empty()
-                    || query.selectors.iter().any(|selector| {
-                        selector.cols().any(|(_, find_operator)| {
-                            if let FindOperator::In(vals) = find_operator {
-                                vals.is_empty()
-                            } else {
-                                false
+                let Self { executor, dialect, transactional_chunks } = self;
+
+                if query.insert_query.values.is_empty()
+                    || query
+                        .insert_query
+                        .values
+                        .iter()
+                        .all(|input_record| input_record.is_empty())
+                {
+                    Box::pin(async { Ok(0) })
+                } else {
+                    Box::pin(async move {
+                        let UpsertQuery { insert_query, on_conflict } = query;
+                        let InsertQuery { table_name, values } = insert_query;
+                        let mut chunks = chunk_input_records(values, dialect.max_parameters());
+
+                        if chunks.len() == 1 {
+                            let query = UpsertQuery {
+                                insert_query: InsertQuery { table_name, values: chunks.pop().unwrap() },
+                                on_conflict,
+                            };
+
+                            Ok(execute_chunk!(executor, &dialect, query, query.insert_query.values).rows_affected())
+                        } else {
+                            let mut conn = ChunkConnection::open(executor, transactional_chunks).await?;
+                            let mut rows_affected = 0;
+
+                            for values in chunks {
+                                let query = UpsertQuery {
+                                    insert_query: InsertQuery { table_name, values },
+                                    on_conflict: on_conflict.clone(),
+                                };
+
+                                rows_affected += execute_chunk!(conn.get(), &dialect, query, query.insert_query.values).rows_affected();
                             }
-                        })
+
+                            conn.finish().await?;
+
+                            Ok(rows_affected)
+                        }
                     })
+                }
+            }
+
+            fn update<'q, 'o>(
+                self,
+                query: crate::manager::UpdateQuery<'q, $DB>,
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                let Self { executor, dialect, .. } = self;
+
+                if query.filter.matches_nothing()
                     || query.new_values.is_empty()
                 {
-                    Box::pin(async { Ok(()) })
+                    Box::pin(async { Ok(0) })
                 } else {
-                    Box::pin(async {
-                        let sql = query.to_string();
+                    Box::pin(async move {
+                        let sql = to_sql(&query, &dialect)?;
                         let sqlx_query =
-                            create_sqlx_query(&sql, query.selectors, vec![query.new_values]);
+                            create_sqlx_query(&sql, query.filter, vec![query.new_values]);
 
-                        self.execute(sqlx_query).await?;
+                        let result = executor.execute(sqlx_query).await?;
 
-                        Ok(())
+                        Ok(result.rows_affected())
+                    })
+                }
+            }
+
+            fn update_returning<'q, 'o>(
+                self,
+                query: crate::manager::UpdateReturningQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                let Self { executor, dialect, .. } = self;
+
+                if query.update_query.filter.matches_nothing()
+                    || query.update_query.new_values.is_empty()
+                {
+                    Box::pin(futures::stream::empty())
+                } else {
+                    Box::pin(async_stream::try_stream! {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(
+                            &sql,
+                            query.update_query.filter,
+                            vec![query.update_query.new_values],
+                        );
+
+                        for await result in executor.fetch(sqlx_query) {
+                            let row = result?;
+                            let record = Record::from_row(row);
+                            yield record
+                        }
                     })
                 }
             }
@@ -200,40 +700,51 @@ macro_rules! impl_manager_for_db_executor {
             fn delete<'q, 'o>(
                 self,
                 query: crate::manager::DeleteQuery<'q, $DB>,
-            ) -> futures::future::BoxFuture<'o, sqlx::Result<()>>
+            ) -> futures::future::BoxFuture<'o, sqlx::Result<u64>>
             where
                 'm: 'o,
                 'q: 'o,
             {
-                if query.selectors.is_empty()
-                    || query.selectors.iter().any(|selector| {
-                        selector.cols().any(|(_, find_operator)| {
-                            if let FindOperator::In(vals) = find_operator {
-                                vals.is_empty()
-                            } else {
-                                false
-                            }
-                        })
-                    })
+                let Self { executor, dialect, .. } = self;
+
+                if query.filter.matches_nothing()
                 {
-                    Box::pin(async { Ok(()) })
+                    Box::pin(async { Ok(0) })
                 } else {
-                    Box::pin(async {
-                        let sql = if query.selectors.iter().any(Selector::is_empty) {
-                            DeleteQuery::<$DB> {
-                                table_name: query.table_name,
-                                selectors: vec![Selector::new()],
-                            }
-                            .to_string()
-                        } else {
-                            query.to_string()
-                        };
+                    Box::pin(async move {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(&sql, query.filter, vec![]);
 
-                        let sqlx_query = create_sqlx_query(&sql, query.selectors, vec![]);
+                        let result = executor.execute(sqlx_query).await?;
 
-                        self.execute(sqlx_query).await?;
+                        Ok(result.rows_affected())
+                    })
+                }
+            }
 
-                        Ok(())
+            fn delete_returning<'q, 'o>(
+                self,
+                query: crate::manager::DeleteReturningQuery<'q, $DB>,
+            ) -> futures::stream::BoxStream<'o, sqlx::Result<crate::manager::Record<$DB>>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                let Self { executor, dialect, .. } = self;
+
+                if query.delete_query.filter.matches_nothing()
+                {
+                    Box::pin(futures::stream::empty())
+                } else {
+                    Box::pin(async_stream::try_stream! {
+                        let sql = to_sql(&query, &dialect)?;
+                        let sqlx_query = create_sqlx_query(&sql, query.delete_query.filter, vec![]);
+
+                        for await result in executor.fetch(sqlx_query) {
+                            let row = result?;
+                            let record = Record::from_row(row);
+                            yield record
+                        }
                     })
                 }
             }
@@ -246,23 +757,98 @@ macro_rules! impl_manager_for_db_executor {
                 'm: 'o,
                 'q: 'o,
             {
+                let Self { executor, .. } = self;
+
                 Box::pin(async_stream::try_stream! {
-                    for await result in self.fetch(query) {
+                    for await result in executor.fetch(query) {
                         let row = result?;
                         let record = Record::from_row(row);
                         yield record
                     }
                 })
             }
+
+            fn visit_dialect<R>(&self, visit: impl FnOnce(&dyn Dialect) -> R) -> R {
+                visit(&self.dialect)
+            }
+        }
+
+        impl<'m, T> Begin<'m, $DB> for T
+        where
+            T: ExecutorDialect<'m, $DB> + Acquire<'m, Database = $DB>,
+        {
+            type Transaction = sqlx::Transaction<'m, $DB>;
+
+            fn begin(self) -> futures::future::BoxFuture<'m, sqlx::Result<Self::Transaction>> {
+                Acquire::begin(self)
+            }
+        }
+
+        impl<'c> Transaction<'c, $DB> for sqlx::Transaction<'c, $DB> {
+            type Error = sqlx::Error;
+            type Manager<'t>
+                = &'t mut <$DB as sqlx::Database>::Connection
+            where
+                Self: 't;
+            type Nested<'t>
+                = sqlx::Transaction<'t, $DB>
+            where
+                Self: 't;
+
+            fn manager(&mut self) -> Self::Manager<'_> {
+                &mut **self
+            }
+
+            fn begin(&mut self) -> futures::future::BoxFuture<'_, sqlx::Result<Self::Nested<'_>>> {
+                Acquire::begin(self)
+            }
+
+            fn commit(self) -> futures::future::BoxFuture<'c, sqlx::Result<()>> {
+                Box::pin(sqlx::Transaction::commit(self))
+            }
+
+            fn rollback(self) -> futures::future::BoxFuture<'c, sqlx::Result<()>> {
+                Box::pin(sqlx::Transaction::rollback(self))
+            }
         }
     };
 }
 
+trait GeneratedId {
+    fn first_generated_id(&self) -> Option<u64>;
+}
+
 #[cfg(feature = "mysql")]
-impl_manager_for_db_executor!(sqlx::MySql);
+impl GeneratedId for sqlx::mysql::MySqlQueryResult {
+    fn first_generated_id(&self) -> Option<u64> {
+        Some(self.last_insert_id())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl GeneratedId for sqlx::postgres::PgQueryResult {
+    fn first_generated_id(&self) -> Option<u64> {
+        None
+    }
+}
 
-#[cfg(feature = "mssql")]
-impl_manager_for_db_executor!(sqlx::Mssql);
+#[cfg(feature = "sqlite")]
+impl GeneratedId for sqlx::sqlite::SqliteQueryResult {
+    fn first_generated_id(&self) -> Option<u64> {
+        let first = self.last_insert_rowid() - i64::try_from(self.rows_affected()).ok()? + 1;
+        u64::try_from(first).ok()
+    }
+}
+
+#[cfg(feature = "any")]
+impl GeneratedId for sqlx::any::AnyQueryResult {
+    fn first_generated_id(&self) -> Option<u64> {
+        self.last_insert_id().and_then(|id| u64::try_from(id).ok())
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl_manager_for_db_executor!(sqlx::MySql);
 
 #[cfg(feature = "postgres")]
 impl_manager_for_db_executor!(sqlx::Postgres);
@@ -273,13 +859,123 @@ impl_manager_for_db_executor!(sqlx::Sqlite);
 #[cfg(feature = "any")]
 impl_manager_for_db_executor!(sqlx::Any);
 
+enum ChunkConnection<'c, DB: Database, C> {
+    Connection(C),
+    Transaction(sqlx::Transaction<'c, DB>),
+}
+
+impl<'c, DB, C> ChunkConnection<'c, DB, C>
+where
+    DB: Database,
+    C: DerefMut<Target = DB::Connection>,
+{
+    async fn open<T: Acquire<'c, Database = DB, Connection = C>>(
+        executor: T,
+        transactional: bool,
+    ) -> sqlx::Result<Self> {
+        Ok(if transactional {
+            Self::Transaction(executor.begin().await?)
+        } else {
+            Self::Connection(executor.acquire().await?)
+        })
+    }
+
+    fn get(&mut self) -> &mut DB::Connection {
+        match self {
+            Self::Connection(conn) => conn,
+            Self::Transaction(transaction) => transaction,
+        }
+    }
+
+    async fn finish(self) -> sqlx::Result<()> {
+        match self {
+            Self::Connection(_) => Ok(()),
+            Self::Transaction(transaction) => transaction.commit().await,
+        }
+    }
+}
+
+pub(crate) fn chunk_input_records<DB: Database>(
+    values: Vec<InputRecord<'_, DB>>,
+    max_parameters: usize,
+) -> Vec<Vec<InputRecord<'_, DB>>> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    let mut parameters = 0;
+
+    for input_record in values {
+        let len = input_record.cols().len();
+
+        if !chunk.is_empty() && parameters + len > max_parameters {
+            chunks.push(std::mem::take(&mut chunk));
+            parameters = 0;
+        }
+
+        parameters += len;
+        chunk.push(input_record);
+    }
+
+    chunks.push(chunk);
+    chunks
+}
+
+pub(crate) fn chunk_select_query<DB: Database>(
+    mut query: SelectQuery<'_, DB>,
+    max_parameters: usize,
+) -> Vec<SelectQuery<'_, DB>> {
+    let (col_name, values) = match &mut query.filter {
+        Filter::Condition(col_name, FindOperator::In(values))
+            if values.len() > max_parameters
+                && query.order_by.is_none()
+                && query.offset.is_none()
+                && query.limit.is_none() =>
+        {
+            (*col_name, std::mem::take(values))
+        }
+        _ => return vec![query],
+    };
+
+    let mut values = values.into_iter();
+    let mut queries = Vec::new();
+
+    loop {
+        let chunk = values
+            .by_ref()
+            .take(max_parameters.max(1))
+            .collect::<Vec<_>>();
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        queries.push(SelectQuery {
+            table_name: query.table_name,
+            col_names: query.col_names.clone(),
+            filter: Filter::Condition(col_name, FindOperator::In(chunk)),
+            order_by: None,
+            offset: None,
+            limit: None,
+        });
+    }
+
+    queries
+}
+
+fn to_sql<Q: ToSql + ?Sized, D: Dialect + ?Sized>(query: &Q, dialect: &D) -> sqlx::Result<String> {
+    query
+        .validate(dialect)
+        .map_err(|err| sqlx::Error::Configuration(Box::new(err)))?;
+
+    Ok(query.display(dialect).to_string())
+}
+
 fn create_sqlx_query<'s, 'q: 's, DB: Database>(
     sql: &'s str,
-    selectors: Vec<Selector<'q, DB>>,
+    filter: Filter<'q, DB>,
     input_records: Vec<InputRecord<'q, DB>>,
-) -> sqlx::query::Query<'s, DB, <DB as HasArguments<'s>>::Arguments> {
+) -> Query<'s, DB, <DB as HasArguments<'s>>::Arguments> {
     // https://github.com/launchbadge/sqlx/issues/1428#issuecomment-1002818746
-    let selectors = unsafe { std::mem::transmute::<_, Vec<Selector<'s, DB>>>(selectors) };
+    let filter = unsafe { std::mem::transmute::<_, Filter<'s, DB>>(filter) };
     let input_records =
         unsafe { std::mem::transmute::<_, Vec<InputRecord<'s, DB>>>(input_records) };
 
@@ -291,17 +987,52 @@ fn create_sqlx_query<'s, 'q: 's, DB: Database>(
         }
     }
 
-    for selector in selectors {
-        for (_, op) in selector.into_cols() {
-            match op {
-                FindOperator::Eq(val) | FindOperator::Ne(val) => sqlx_query = val.bind(sqlx_query),
-                FindOperator::In(vals) | FindOperator::NotIn(vals) => {
-                    for val in vals {
+    bind_filter(sqlx_query, filter)
+}
+
+fn bind_filter<'q, DB: Database>(
+    mut sqlx_query: Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+    filter: Filter<'q, DB>,
+) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
+    match filter {
+        Filter::Condition(_, op) => match op {
+            FindOperator::Eq(val) | FindOperator::Ne(val) => {
+                if !val.is_null() {
+                    sqlx_query = val.bind(sqlx_query);
+                }
+            }
+            FindOperator::In(vals) | FindOperator::NotIn(vals) => {
+                for val in vals {
+                    if !val.is_null() {
                         sqlx_query = val.bind(sqlx_query);
                     }
                 }
             }
+            FindOperator::Lt(val)
+            | FindOperator::Le(val)
+            | FindOperator::Gt(val)
+            | FindOperator::Ge(val)
+            | FindOperator::Like(val)
+            | FindOperator::NotLike(val)
+            | FindOperator::ILike(val) => sqlx_query = val.bind(sqlx_query),
+            FindOperator::Between(low, high) => {
+                sqlx_query = low.bind(sqlx_query);
+                sqlx_query = high.bind(sqlx_query);
+            }
+        },
+        Filter::Row(_, _, vals) => {
+            for val in vals {
+                sqlx_query = val.bind(sqlx_query);
+            }
+        }
+        Filter::InSubquery(_, subquery) => sqlx_query = bind_filter(sqlx_query, subquery.filter),
+        Filter::Exists(exists) => sqlx_query = bind_filter(sqlx_query, exists.filter),
+        Filter::And(filters) | Filter::Or(filters) => {
+            for filter in filters {
+                sqlx_query = bind_filter(sqlx_query, filter);
+            }
         }
+        Filter::Not(filter) => sqlx_query = bind_filter(sqlx_query, *filter),
     }
 
     sqlx_query
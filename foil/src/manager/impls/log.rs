@@ -1,8 +1,12 @@
 use crate::{
-    manager::{display::WithBindParameters, Record},
+    manager::{
+        impls::executor::{chunk_input_records, chunk_select_query},
+        Begin, Dialect, Filter, FindOperator, InsertQuery, InvalidQuery, Record, SelectQuery,
+        ToSql, Transaction, WithDialect,
+    },
     Manager,
 };
-use futures::{stream::BoxStream, TryFutureExt, TryStreamExt};
+use futures::{future::BoxFuture, stream::BoxStream, TryFutureExt, TryStreamExt};
 use sqlx::Database;
 use thiserror::Error;
 
@@ -15,10 +19,95 @@ impl<M, W: std::fmt::Write + Send> LogManager<M, W> {
     pub fn new(inner: M, writer: W) -> Self {
         Self { inner, writer }
     }
+
+    fn log<'m, DB, Q>(&mut self, query: &Q) -> Result<(), Error<M::Error>>
+    where
+        DB: Database + WithDialect,
+        M: Manager<'m, DB>,
+        Q: ToSql,
+    {
+        let Self { inner, writer } = self;
+
+        inner.visit_dialect(|dialect| {
+            query.validate(dialect)?;
+            write!(writer, "{}", query.display(dialect))?;
+            Ok(())
+        })
+    }
+
+    fn log_select_chunks<'m, 'q, DB>(
+        &mut self,
+        query: SelectQuery<'q, DB>,
+    ) -> (SelectQuery<'q, DB>, Result<(), Error<M::Error>>)
+    where
+        DB: Database + WithDialect,
+        M: Manager<'m, DB>,
+    {
+        let max_parameters = self.inner.visit_dialect(|dialect| dialect.max_parameters());
+        let mut chunks = chunk_select_query(query, max_parameters).into_iter();
+        let mut query = chunks.next().unwrap();
+        let mut result = self.log(&query);
+
+        for chunk in chunks {
+            if result.is_ok() {
+                result = writeln!(self.writer, ";").map_err(Error::from);
+                result = result.and_then(|()| self.log(&chunk));
+            }
+
+            if let (
+                Filter::Condition(_, FindOperator::In(values)),
+                Filter::Condition(_, FindOperator::In(chunk_values)),
+            ) = (&mut query.filter, chunk.filter)
+            {
+                values.extend(chunk_values);
+            }
+        }
+
+        (query, result)
+    }
+
+    fn log_chunks<'m, 'q, DB, Q>(
+        &mut self,
+        query: &mut Q,
+        insert_query: impl Fn(&mut Q) -> &mut InsertQuery<'q, DB>,
+    ) -> Result<(), Error<M::Error>>
+    where
+        DB: Database + WithDialect,
+        M: Manager<'m, DB>,
+        Q: ToSql,
+    {
+        let max_parameters = self.inner.visit_dialect(|dialect| dialect.max_parameters());
+        let values = std::mem::take(&mut insert_query(query).values);
+        let mut logged_values = Vec::with_capacity(values.len());
+        let mut result = Ok(());
+
+        for (i, chunk) in chunk_input_records(values, max_parameters)
+            .into_iter()
+            .enumerate()
+        {
+            insert_query(query).values = chunk;
+
+            if result.is_ok() {
+                if i > 0 {
+                    result = writeln!(self.writer, ";").map_err(Error::from);
+                }
+
+                result = result.and_then(|()| self.log(query));
+            }
+
+            logged_values.append(&mut insert_query(query).values);
+        }
+
+        insert_query(query).values = logged_values;
+        result
+    }
 }
 
-impl<'m, DB: Database + WithBindParameters, M: Manager<'m, DB>, W: std::fmt::Write + Send>
-    Manager<'m, DB> for LogManager<M, W>
+impl<'m, DB, M, W> Manager<'m, DB> for LogManager<M, W>
+where
+    DB: Database + WithDialect,
+    M: Manager<'m, DB>,
+    W: std::fmt::Write + Send,
 {
     type Error = Error<M::Error>;
 
@@ -30,8 +119,10 @@ impl<'m, DB: Database + WithBindParameters, M: Manager<'m, DB>, W: std::fmt::Wri
         'm: 'o,
         'q: 'o,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::stream::once(async move { Err(err.into()) }));
+        let (query, result) = self.log_select_chunks(query);
+
+        if let Err(err) = result {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
         }
 
         Box::pin(self.inner.select(query).map_err(Error::Inner))
@@ -47,23 +138,38 @@ impl<'m, DB: Database + WithBindParameters, M: Manager<'m, DB>, W: std::fmt::Wri
         for<'a> i64: sqlx::Type<DB> + sqlx::Decode<'a, DB>,
         for<'a> &'a str: sqlx::ColumnIndex<<DB as Database>::Row>,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::future::ready(Err(err.into())));
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::future::ready(Err(err)));
         }
 
         Box::pin(self.inner.count(query).map_err(Error::Inner))
     }
 
+    fn aggregate<'q, 'o>(
+        mut self,
+        query: crate::manager::AggregateQuery<'q, DB>,
+    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o,
+    {
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
+        }
+
+        Box::pin(self.inner.aggregate(query).map_err(Error::Inner))
+    }
+
     fn insert<'q, 'o>(
         mut self,
-        query: crate::manager::InsertQuery<'q, DB>,
+        mut query: crate::manager::InsertQuery<'q, DB>,
     ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
     where
         'm: 'o,
         'q: 'o,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::future::ready(Err(err.into())));
+        if let Err(err) = self.log_chunks(&mut query, |query| query) {
+            return Box::pin(futures::future::ready(Err(err)));
         }
 
         Box::pin(self.inner.insert(query).map_err(Error::Inner))
@@ -71,49 +177,117 @@ impl<'m, DB: Database + WithBindParameters, M: Manager<'m, DB>, W: std::fmt::Wri
 
     fn insert_returning<'q, 'o>(
         mut self,
-        query: crate::manager::InsertReturningQuery<'q, DB>,
+        mut query: crate::manager::InsertReturningQuery<'q, DB>,
     ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<DB>, Self::Error>>
     where
         'm: 'o,
         'q: 'o,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::stream::once(async move { Err(err.into()) }));
+        if let Err(err) = self.log_chunks(&mut query, |query| &mut query.insert_query) {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
         }
 
         Box::pin(self.inner.insert_returning(query).map_err(Error::Inner))
     }
 
+    fn insert_unnest<'q, 'o>(
+        mut self,
+        mut query: crate::manager::UnnestInsertQuery<'q, DB>,
+    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o,
+    {
+        let result = if self.inner.visit_dialect(|dialect| dialect.native_unnest()) {
+            self.log(&query)
+        } else {
+            self.log_chunks(&mut query.insert_returning_query, |query| {
+                &mut query.insert_query
+            })
+        };
+
+        if let Err(err) = result {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
+        }
+
+        Box::pin(self.inner.insert_unnest(query).map_err(Error::Inner))
+    }
+
+    fn upsert<'q, 'o>(
+        mut self,
+        mut query: crate::manager::UpsertQuery<'q, DB>,
+    ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o,
+    {
+        if let Err(err) = self.log_chunks(&mut query, |query| &mut query.insert_query) {
+            return Box::pin(futures::future::ready(Err(err)));
+        }
+
+        Box::pin(self.inner.upsert(query).map_err(Error::Inner))
+    }
+
     fn update<'q, 'o>(
         mut self,
         query: crate::manager::UpdateQuery<'q, DB>,
-    ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
+    ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
     where
         'm: 'o,
         'q: 'o,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::future::ready(Err(err.into())));
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::future::ready(Err(err)));
         }
 
         Box::pin(self.inner.update(query).map_err(Error::Inner))
     }
 
+    fn update_returning<'q, 'o>(
+        mut self,
+        query: crate::manager::UpdateReturningQuery<'q, DB>,
+    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o,
+    {
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
+        }
+
+        Box::pin(self.inner.update_returning(query).map_err(Error::Inner))
+    }
+
     fn delete<'q, 'o>(
         mut self,
         query: crate::manager::DeleteQuery<'q, DB>,
-    ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
+    ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
     where
         'm: 'o,
         'q: 'o,
     {
-        if let Err(err) = write!(self.writer, "{}", query) {
-            return Box::pin(futures::future::ready(Err(err.into())));
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::future::ready(Err(err)));
         }
 
         Box::pin(self.inner.delete(query).map_err(Error::Inner))
     }
 
+    fn delete_returning<'q, 'o>(
+        mut self,
+        query: crate::manager::DeleteReturningQuery<'q, DB>,
+    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o,
+    {
+        if let Err(err) = self.log(&query) {
+            return Box::pin(futures::stream::once(async move { Err(err) }));
+        }
+
+        Box::pin(self.inner.delete_returning(query).map_err(Error::Inner))
+    }
+
     fn query<'q, 'o, Q: sqlx::Execute<'q, DB> + 'q>(
         mut self,
         query: Q,
@@ -128,6 +302,70 @@ impl<'m, DB: Database + WithBindParameters, M: Manager<'m, DB>, W: std::fmt::Wri
 
         Box::pin(self.inner.query(query).map_err(Error::Inner))
     }
+
+    fn visit_dialect<R>(&self, visit: impl FnOnce(&dyn Dialect) -> R) -> R {
+        self.inner.visit_dialect(visit)
+    }
+}
+
+impl<'m, DB, M, W> Begin<'m, DB> for LogManager<M, W>
+where
+    DB: Database + WithDialect,
+    M: Begin<'m, DB>,
+    W: std::fmt::Write + Send + 'm,
+{
+    type Transaction = LogManager<M::Transaction, W>;
+
+    fn begin(self) -> BoxFuture<'m, Result<Self::Transaction, Self::Error>> {
+        let Self { inner, writer } = self;
+
+        Box::pin(
+            inner
+                .begin()
+                .map_ok(move |transaction| LogManager::new(transaction, writer))
+                .map_err(Error::Inner),
+        )
+    }
+}
+
+impl<'c, DB, T, W> Transaction<'c, DB> for LogManager<T, W>
+where
+    DB: Database + WithDialect,
+    T: Transaction<'c, DB>,
+    W: std::fmt::Write + Send + 'c,
+{
+    type Error = Error<T::Error>;
+    type Manager<'t>
+        = LogManager<T::Manager<'t>, &'t mut W>
+    where
+        Self: 't;
+    type Nested<'t>
+        = LogManager<T::Nested<'t>, &'t mut W>
+    where
+        Self: 't;
+
+    fn manager(&mut self) -> Self::Manager<'_> {
+        LogManager::new(self.inner.manager(), &mut self.writer)
+    }
+
+    fn begin(&mut self) -> BoxFuture<'_, Result<Self::Nested<'_>, Self::Error>> {
+        let writer = &mut self.writer;
+
+        Box::pin(
+            self.inner
+                .begin()
+                .map_ok(move |transaction| LogManager::new(transaction, writer))
+                .map_err(Error::Inner),
+        )
+    }
+
+    fn commit(self) -> BoxFuture<'c, Result<(), Self::Error>> {
+        Box::pin(self.inner.commit().map_err(Error::Inner))
+    }
+
+    fn rollback(self) -> BoxFuture<'c, Result<(), Self::Error>> {
+        Box::pin(self.inner.rollback().map_err(Error::Inner))
+    }
 }
 
 #[derive(Debug, Error)]
@@ -135,5 +373,7 @@ pub enum Error<Inner> {
     #[error("Error logging SQL: {0}")]
     Fmt(#[from] std::fmt::Error),
     #[error(transparent)]
+    Invalid(#[from] InvalidQuery),
+    #[error(transparent)]
     Inner(Inner),
 }
@@ -1,15 +1,20 @@
 use std::str::FromStr;
 
 use crate::manager::impls::log::{Error, LogManager};
-use crate::manager::Record;
+use crate::manager::{
+    Begin, Dialect, DialectManager, Record, SqliteDialect, Transaction, UpsertSyntax,
+};
 use crate::Manager;
+use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use sqlx::sqlite::SqliteConnectOptions;
-use sqlx::{ConnectOptions, Executor, Sqlite, SqliteConnection};
+use sqlx::{Acquire, ConnectOptions, Executor, Sqlite, SqliteConnection};
+use std::fmt::{self, Write};
 
 pub struct MockManager {
     history: Vec<String>,
     conn: SqliteConnection,
+    dialect: MockDialect,
 }
 
 impl MockManager {
@@ -20,9 +25,16 @@ impl MockManager {
         Ok(Self {
             history: vec![],
             conn,
+            dialect: MockDialect::default(),
         })
     }
 
+    #[must_use]
+    pub fn dialect(mut self, dialect: MockDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
     pub async fn exec_sql(&mut self, sql: &str) -> sqlx::Result<sqlx::sqlite::SqliteQueryResult> {
         let result = self.conn.execute(sql).await?;
         self.history.push(sql.into());
@@ -32,97 +44,322 @@ impl MockManager {
     pub fn last_statement(&self) -> Option<&str> {
         self.history.last().map(String::as_str)
     }
+
+    fn parts(&mut self) -> (&mut Vec<String>, &mut SqliteConnection, MockDialect) {
+        (&mut self.history, &mut self.conn, self.dialect)
+    }
 }
 
-macro_rules! record_and_delegate {
-    ($self:expr, $query:expr, $method:ident) => {{
-        let mut sql = String::new();
-        let manager = LogManager::new(&mut $self.conn, &mut sql);
-        let stream = manager.$method($query);
-        $self.history.push(sql);
-        stream
-    }};
+#[derive(Clone, Copy)]
+pub struct MockDialect {
+    pub max_parameters: usize,
+    pub supports_returning: bool,
+    pub upsert_syntax: UpsertSyntax,
 }
 
-impl<'m> Manager<'m, Sqlite> for &'m mut MockManager {
-    type Error = Error<sqlx::Error>;
+impl Default for MockDialect {
+    fn default() -> Self {
+        Self {
+            max_parameters: SqliteDialect.max_parameters(),
+            supports_returning: SqliteDialect.supports_returning(),
+            upsert_syntax: SqliteDialect.upsert_syntax(),
+        }
+    }
+}
 
-    fn select<'q, 'o>(
-        self,
-        query: crate::manager::SelectQuery<'q, Sqlite>,
-    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, select)
+impl Dialect for MockDialect {
+    fn write_ident(&self, w: &mut dyn Write, ident: &str) -> fmt::Result {
+        SqliteDialect.write_ident(w, ident)
     }
 
-    fn count<'q, 'o>(
-        self,
-        query: crate::manager::CountQuery<'q, Sqlite>,
-    ) -> futures::future::BoxFuture<'o, Result<i64, Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-        for<'a> i64: sqlx::Type<Sqlite> + sqlx::Decode<'a, Sqlite>,
-        for<'a> &'a str: sqlx::ColumnIndex<<Sqlite as sqlx::Database>::Row>,
-    {
-        record_and_delegate!(self, query, count)
-    }
-
-    fn insert<'q, 'o>(
-        self,
-        query: crate::manager::InsertQuery<'q, Sqlite>,
-    ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, insert)
+    fn parameter(&self, index: usize) -> String {
+        SqliteDialect.parameter(index)
     }
 
-    fn insert_returning<'q, 'o>(
-        self,
-        query: crate::manager::InsertReturningQuery<'q, Sqlite>,
-    ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, insert_returning)
+    fn max_parameters(&self) -> usize {
+        self.max_parameters
     }
 
-    fn update<'q, 'o>(
-        self,
-        query: crate::manager::UpdateQuery<'q, Sqlite>,
-    ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, update)
+    fn write_limit_offset(
+        &self,
+        w: &mut dyn Write,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        ordered: bool,
+    ) -> fmt::Result {
+        SqliteDialect.write_limit_offset(w, limit, offset, ordered)
     }
 
-    fn delete<'q, 'o>(
-        self,
-        query: crate::manager::DeleteQuery<'q, Sqlite>,
-    ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
-    where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, delete)
+    fn supports_returning(&self) -> bool {
+        self.supports_returning
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        self.upsert_syntax
     }
 
-    fn query<'q, 'o, Q: sqlx::Execute<'q, Sqlite> + 'q>(
-        self,
-        query: Q,
-    ) -> BoxStream<'o, Result<Record<Sqlite>, Self::Error>>
+    fn native_ilike(&self) -> bool {
+        SqliteDialect.native_ilike()
+    }
+
+    fn explicit_like_escape(&self) -> bool {
+        SqliteDialect.explicit_like_escape()
+    }
+
+    fn native_nulls_order(&self) -> bool {
+        SqliteDialect.native_nulls_order()
+    }
+}
+
+pub struct MockTransaction<'c> {
+    history: &'c mut Vec<String>,
+    transaction: sqlx::Transaction<'c, Sqlite>,
+    dialect: MockDialect,
+    depth: usize,
+}
+
+impl MockTransaction<'_> {
+    pub fn last_statement(&self) -> Option<&str> {
+        self.history.last().map(String::as_str)
+    }
+
+    fn parts(&mut self) -> (&mut Vec<String>, &mut SqliteConnection, MockDialect) {
+        (self.history, &mut *self.transaction, self.dialect)
+    }
+}
+
+impl<'m> Begin<'m, Sqlite> for &'m mut MockManager {
+    type Transaction = MockTransaction<'m>;
+
+    fn begin(self) -> BoxFuture<'m, Result<Self::Transaction, Self::Error>> {
+        let (history, conn, dialect) = self.parts();
+
+        Box::pin(async move {
+            let transaction = Acquire::begin(conn).await.map_err(Error::Inner)?;
+            history.push("BEGIN".into());
+
+            Ok(MockTransaction {
+                history,
+                transaction,
+                dialect,
+                depth: 1,
+            })
+        })
+    }
+}
+
+impl<'c> Transaction<'c, Sqlite> for MockTransaction<'c> {
+    type Error = Error<sqlx::Error>;
+    type Manager<'t>
+        = &'t mut MockTransaction<'c>
     where
-        'm: 'o,
-        'q: 'o,
-    {
-        record_and_delegate!(self, query, query)
+        Self: 't;
+    type Nested<'t>
+        = MockTransaction<'t>
+    where
+        Self: 't;
+
+    fn manager(&mut self) -> Self::Manager<'_> {
+        self
+    }
+
+    fn begin(&mut self) -> BoxFuture<'_, Result<Self::Nested<'_>, Self::Error>> {
+        Box::pin(async move {
+            let transaction = Acquire::begin(&mut self.transaction)
+                .await
+                .map_err(Error::Inner)?;
+            self.history
+                .push(format!("SAVEPOINT _sqlx_savepoint_{}", self.depth));
+
+            Ok(MockTransaction {
+                history: self.history,
+                transaction,
+                dialect: self.dialect,
+                depth: self.depth + 1,
+            })
+        })
+    }
+
+    fn commit(self) -> BoxFuture<'c, Result<(), Self::Error>> {
+        Box::pin(async move {
+            self.transaction.commit().await.map_err(Error::Inner)?;
+            self.history.push(if self.depth == 1 {
+                "COMMIT".into()
+            } else {
+                format!("RELEASE SAVEPOINT _sqlx_savepoint_{}", self.depth - 1)
+            });
+
+            Ok(())
+        })
+    }
+
+    fn rollback(self) -> BoxFuture<'c, Result<(), Self::Error>> {
+        Box::pin(async move {
+            self.transaction.rollback().await.map_err(Error::Inner)?;
+            self.history.push(if self.depth == 1 {
+                "ROLLBACK".into()
+            } else {
+                format!("ROLLBACK TO SAVEPOINT _sqlx_savepoint_{}", self.depth - 1)
+            });
+
+            Ok(())
+        })
     }
 }
+
+macro_rules! record_and_delegate {
+    ($self:expr, $query:expr, $method:ident) => {{
+        let (history, conn, dialect) = $self.parts();
+        let mut sql = String::new();
+        let manager = LogManager::new(DialectManager::new(conn, dialect), &mut sql);
+        let stream = manager.$method($query);
+        history.push(sql);
+        stream
+    }};
+}
+
+macro_rules! impl_manager_for_mock {
+    (<$($lifetime:lifetime),+> $ty:ty) => {
+        impl<$($lifetime),+> Manager<'m, Sqlite> for $ty {
+            type Error = Error<sqlx::Error>;
+
+            fn select<'q, 'o>(
+                self,
+                query: crate::manager::SelectQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, select)
+            }
+
+            fn count<'q, 'o>(
+                self,
+                query: crate::manager::CountQuery<'q, Sqlite>,
+            ) -> futures::future::BoxFuture<'o, Result<i64, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+                for<'a> i64: sqlx::Type<Sqlite> + sqlx::Decode<'a, Sqlite>,
+                for<'a> &'a str: sqlx::ColumnIndex<<Sqlite as sqlx::Database>::Row>,
+            {
+                record_and_delegate!(self, query, count)
+            }
+
+            fn aggregate<'q, 'o>(
+                self,
+                query: crate::manager::AggregateQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, aggregate)
+            }
+
+            fn insert<'q, 'o>(
+                self,
+                query: crate::manager::InsertQuery<'q, Sqlite>,
+            ) -> futures::future::BoxFuture<'o, Result<(), Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, insert)
+            }
+
+            fn insert_returning<'q, 'o>(
+                self,
+                query: crate::manager::InsertReturningQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, insert_returning)
+            }
+
+            fn insert_unnest<'q, 'o>(
+                self,
+                query: crate::manager::UnnestInsertQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, insert_unnest)
+            }
+
+            fn upsert<'q, 'o>(
+                self,
+                query: crate::manager::UpsertQuery<'q, Sqlite>,
+            ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, upsert)
+            }
+
+            fn update<'q, 'o>(
+                self,
+                query: crate::manager::UpdateQuery<'q, Sqlite>,
+            ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, update)
+            }
+
+            fn update_returning<'q, 'o>(
+                self,
+                query: crate::manager::UpdateReturningQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, update_returning)
+            }
+
+            fn delete<'q, 'o>(
+                self,
+                query: crate::manager::DeleteQuery<'q, Sqlite>,
+            ) -> futures::future::BoxFuture<'o, Result<u64, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, delete)
+            }
+
+            fn delete_returning<'q, 'o>(
+                self,
+                query: crate::manager::DeleteReturningQuery<'q, Sqlite>,
+            ) -> futures::stream::BoxStream<'o, Result<crate::manager::Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, delete_returning)
+            }
+
+            fn query<'q, 'o, Q: sqlx::Execute<'q, Sqlite> + 'q>(
+                self,
+                query: Q,
+            ) -> BoxStream<'o, Result<Record<Sqlite>, Self::Error>>
+            where
+                'm: 'o,
+                'q: 'o,
+            {
+                record_and_delegate!(self, query, query)
+            }
+        }
+    };
+}
+
+impl_manager_for_mock!(<'m> &'m mut MockManager);
+
+impl_manager_for_mock!(<'m, 'c> &'m mut MockTransaction<'c>);
@@ -2,24 +2,40 @@ pub use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use sqlx::{Database, Decode, Row, Type};
 use std::any::Any;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::error::Error;
 use thiserror::Error;
 
 mod display;
 pub mod impls {
-    mod executor;
+    pub mod executor;
     pub mod log;
     #[cfg(all(feature = "test-manager", feature = "sqlite"))]
     pub mod mock;
 }
+mod transaction;
 mod value;
 
+pub use display::{Dialect, InvalidQuery, Sql, ToSql, UpsertSyntax, WithDialect};
+pub use transaction::{Begin, Transaction};
 pub use value::Value;
 
+#[cfg(feature = "any")]
+pub use display::AnyDialect;
+#[cfg(feature = "mssql-dialect")]
+pub use display::MssqlDialect;
+#[cfg(feature = "mysql")]
+pub use display::MySqlDialect;
+#[cfg(feature = "postgres")]
+pub use display::PostgresDialect;
+#[cfg(feature = "sqlite")]
+pub use display::SqliteDialect;
+
+pub use impls::executor::DialectManager;
 pub use impls::log::LogManager;
 #[cfg(all(feature = "test-manager", feature = "sqlite"))]
-pub use impls::mock::MockManager;
+pub use impls::mock::{MockManager, MockTransaction};
 
 pub trait Manager<'m, DB: Database>: Send {
     type Error: Error + Send + Sync + 'static;
@@ -39,6 +55,14 @@ pub trait Manager<'m, DB: Database>: Send {
         for<'a> i64: Type<DB> + Decode<'a, DB>,
         for<'a> &'a str: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>;
 
+    fn aggregate<'q, 'o>(
+        self,
+        query: AggregateQuery<'q, DB>,
+    ) -> BoxStream<'o, Result<Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o;
+
     fn insert<'q, 'o>(self, query: InsertQuery<'q, DB>) -> BoxFuture<'o, Result<(), Self::Error>>
     where
         'm: 'o,
@@ -52,12 +76,45 @@ pub trait Manager<'m, DB: Database>: Send {
         'm: 'o,
         'q: 'o;
 
-    fn update<'q, 'o>(self, query: UpdateQuery<'q, DB>) -> BoxFuture<'o, Result<(), Self::Error>>
+    fn insert_unnest<'q, 'o>(
+        self,
+        query: UnnestInsertQuery<'q, DB>,
+    ) -> BoxStream<'o, Result<Record<DB>, Self::Error>>
+    where
+        Self: Sized,
+        'm: 'o,
+        'q: 'o,
+    {
+        self.insert_returning(query.insert_returning_query)
+    }
+
+    fn upsert<'q, 'o>(self, query: UpsertQuery<'q, DB>) -> BoxFuture<'o, Result<u64, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o;
+
+    fn update<'q, 'o>(self, query: UpdateQuery<'q, DB>) -> BoxFuture<'o, Result<u64, Self::Error>>
     where
         'm: 'o,
         'q: 'o;
 
-    fn delete<'q, 'o>(self, query: DeleteQuery<'q, DB>) -> BoxFuture<'o, Result<(), Self::Error>>
+    fn update_returning<'q, 'o>(
+        self,
+        query: UpdateReturningQuery<'q, DB>,
+    ) -> BoxStream<'o, Result<Record<DB>, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o;
+
+    fn delete<'q, 'o>(self, query: DeleteQuery<'q, DB>) -> BoxFuture<'o, Result<u64, Self::Error>>
+    where
+        'm: 'o,
+        'q: 'o;
+
+    fn delete_returning<'q, 'o>(
+        self,
+        query: DeleteReturningQuery<'q, DB>,
+    ) -> BoxStream<'o, Result<Record<DB>, Self::Error>>
     where
         'm: 'o,
         'q: 'o;
@@ -69,12 +126,19 @@ pub trait Manager<'m, DB: Database>: Send {
     where
         'm: 'o,
         'q: 'o;
+
+    fn visit_dialect<R>(&self, visit: impl FnOnce(&dyn Dialect) -> R) -> R
+    where
+        DB: WithDialect,
+    {
+        visit(&DB::dialect())
+    }
 }
 
 pub struct SelectQuery<'q, DB: Database> {
     pub table_name: &'q str,
-    pub col_names: &'q [&'q str],
-    pub selectors: Vec<Selector<'q, DB>>,
+    pub col_names: Cow<'q, [&'q str]>,
+    pub filter: Filter<'q, DB>,
     pub order_by: Option<OrderBy<&'q str>>,
     pub offset: Option<u32>,
     pub limit: Option<u32>,
@@ -82,7 +146,50 @@ pub struct SelectQuery<'q, DB: Database> {
 
 pub struct CountQuery<'q, DB: Database> {
     pub table_name: &'q str,
-    pub selectors: Vec<Selector<'q, DB>>,
+    pub filter: Filter<'q, DB>,
+}
+
+pub struct AggregateQuery<'q, DB: Database> {
+    pub table_name: &'q str,
+    pub aggregates: Vec<Aggregate<&'q str>>,
+    pub group_by: Vec<&'q str>,
+    pub filter: Filter<'q, DB>,
+}
+
+pub struct Aggregate<C> {
+    pub function: AggregateFunction,
+    pub col: C,
+}
+
+impl<C> Aggregate<C> {
+    pub fn map_col<T>(self, f: impl FnOnce(C) -> T) -> Aggregate<T> {
+        Aggregate {
+            function: self.function,
+            col: f(self.col),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
 }
 
 pub struct InsertQuery<'q, DB: Database> {
@@ -93,17 +200,171 @@ pub struct InsertQuery<'q, DB: Database> {
 pub struct InsertReturningQuery<'q, DB: Database> {
     pub insert_query: InsertQuery<'q, DB>,
     pub returning_cols: &'q [&'q str],
+    pub generated_key: Option<&'q str>,
+}
+
+pub struct UnnestInsertQuery<'q, DB: Database> {
+    pub insert_returning_query: InsertReturningQuery<'q, DB>,
+    pub columns: InputRecord<'q, DB>,
+}
+
+pub struct UpsertQuery<'q, DB: Database> {
+    pub insert_query: InsertQuery<'q, DB>,
+    pub on_conflict: OnConflict<'q, &'q str>,
+}
+
+#[derive(Clone)]
+pub struct OnConflict<'q, C> {
+    pub target: ConflictTarget<'q, C>,
+    pub action: ConflictAction<C>,
+}
+
+#[derive(Clone)]
+pub enum ConflictTarget<'q, C> {
+    Cols(Vec<C>),
+    Constraint(&'q str),
+}
+
+#[derive(Clone)]
+pub enum ConflictAction<C> {
+    DoNothing,
+    Update(Vec<C>),
+}
+
+impl<'q, C> OnConflict<'q, C> {
+    pub fn map_cols<T>(self, mut f: impl FnMut(C) -> T) -> OnConflict<'q, T> {
+        OnConflict {
+            target: match self.target {
+                ConflictTarget::Cols(cols) => {
+                    ConflictTarget::Cols(cols.into_iter().map(&mut f).collect())
+                }
+                ConflictTarget::Constraint(constraint) => ConflictTarget::Constraint(constraint),
+            },
+            action: match self.action {
+                ConflictAction::DoNothing => ConflictAction::DoNothing,
+                ConflictAction::Update(cols) => {
+                    ConflictAction::Update(cols.into_iter().map(f).collect())
+                }
+            },
+        }
+    }
 }
 
 pub struct UpdateQuery<'q, DB: Database> {
     pub table_name: &'q str,
-    pub selectors: Vec<Selector<'q, DB>>,
+    pub filter: Filter<'q, DB>,
     pub new_values: InputRecord<'q, DB>,
 }
 
+pub struct UpdateReturningQuery<'q, DB: Database> {
+    pub update_query: UpdateQuery<'q, DB>,
+    pub returning_cols: &'q [&'q str],
+}
+
 pub struct DeleteQuery<'q, DB: Database> {
     pub table_name: &'q str,
-    pub selectors: Vec<Selector<'q, DB>>,
+    pub filter: Filter<'q, DB>,
+}
+
+pub struct DeleteReturningQuery<'q, DB: Database> {
+    pub delete_query: DeleteQuery<'q, DB>,
+    pub returning_cols: &'q [&'q str],
+}
+
+pub enum Filter<'q, DB: Database> {
+    Condition(&'q str, FindOperator<Box<dyn Value<'q, DB> + 'q>>),
+    Row(Vec<&'q str>, RowOperator, Vec<Box<dyn Value<'q, DB> + 'q>>),
+    InSubquery(&'q str, Box<Subquery<'q, DB>>),
+    Exists(Box<Exists<'q, DB>>),
+    And(Vec<Filter<'q, DB>>),
+    Or(Vec<Filter<'q, DB>>),
+    Not(Box<Filter<'q, DB>>),
+}
+
+impl<'q, DB: Database> Filter<'q, DB> {
+    #[must_use]
+    pub fn all() -> Self {
+        Self::And(Vec::new())
+    }
+
+    pub fn condition<T: Value<'q, DB> + 'q>(col_name: &'q str, operator: FindOperator<T>) -> Self {
+        Self::Condition(col_name, operator.boxed())
+    }
+
+    pub fn from_selectors<S: IntoSelector<'q, DB>>(selectors: Vec<S>) -> Self {
+        let mut filters = selectors
+            .into_iter()
+            .map(IntoFilter::into_filter)
+            .collect::<Vec<_>>();
+
+        if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Self::Or(filters)
+        }
+    }
+
+    #[must_use]
+    pub fn is_all(&self) -> bool {
+        matches!(self, Self::And(filters) if filters.is_empty())
+    }
+
+    #[must_use]
+    pub fn matches_nothing(&self) -> bool {
+        match self {
+            Self::Condition(_, FindOperator::In(values)) => values.is_empty(),
+            Self::Condition(_, _) | Self::Row(_, _, _) | Self::Not(_) => false,
+            Self::InSubquery(_, subquery) => subquery.filter.matches_nothing(),
+            Self::Exists(exists) => exists.filter.matches_nothing(),
+            Self::And(filters) => filters.iter().any(Self::matches_nothing),
+            Self::Or(filters) => filters.iter().all(Self::matches_nothing),
+        }
+    }
+}
+
+pub struct Subquery<'q, DB: Database> {
+    pub table_name: &'q str,
+    pub col_name: &'q str,
+    pub filter: Filter<'q, DB>,
+}
+
+pub struct Exists<'q, DB: Database> {
+    pub table_name: &'q str,
+    pub col_name: &'q str,
+    pub outer_table_name: &'q str,
+    pub outer_col_name: &'q str,
+    pub filter: Filter<'q, DB>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RowOperator {
+    Lt,
+    Gt,
+}
+
+impl<'q, DB: Database> From<Selector<'q, DB>> for Filter<'q, DB> {
+    fn from(selector: Selector<'q, DB>) -> Self {
+        let mut filters = selector
+            .into_cols()
+            .map(|(col_name, operator)| Self::Condition(col_name, operator))
+            .collect::<Vec<_>>();
+
+        if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            Self::And(filters)
+        }
+    }
+}
+
+pub trait IntoFilter<'q, DB: Database> {
+    fn into_filter(self) -> Filter<'q, DB>;
+}
+
+impl<'q, DB: Database, S: IntoSelector<'q, DB>> IntoFilter<'q, DB> for S {
+    fn into_filter(self) -> Filter<'q, DB> {
+        self.into_selector().into()
+    }
 }
 
 pub struct Selector<'q, DB: Database>(Vec<(&'q str, FindOperator<Box<dyn Value<'q, DB> + 'q>>)>);
@@ -174,11 +435,26 @@ pub trait IntoSelector<'q, DB: Database> {
     fn into_selector(self) -> Selector<'q, DB>;
 }
 
+impl<'q, DB: Database> IntoSelector<'q, DB> for Selector<'q, DB> {
+    fn into_selector(self) -> Selector<'q, DB> {
+        self
+    }
+}
+
+#[derive(Clone)]
 pub enum FindOperator<T> {
     Eq(T),
     Ne(T),
     In(Vec<T>),
     NotIn(Vec<T>),
+    Lt(T),
+    Le(T),
+    Gt(T),
+    Ge(T),
+    Between(T, T),
+    Like(T),
+    NotLike(T),
+    ILike(T),
 }
 
 impl<T> FindOperator<T> {
@@ -199,10 +475,32 @@ impl<T> FindOperator<T> {
                     .map(|val| Box::new(val) as Box<dyn Value<'q, _>>)
                     .collect(),
             ),
+            Self::Lt(val) => FindOperator::Lt(Box::new(val)),
+            Self::Le(val) => FindOperator::Le(Box::new(val)),
+            Self::Gt(val) => FindOperator::Gt(Box::new(val)),
+            Self::Ge(val) => FindOperator::Ge(Box::new(val)),
+            Self::Between(low, high) => FindOperator::Between(Box::new(low), Box::new(high)),
+            Self::Like(val) => FindOperator::Like(Box::new(val)),
+            Self::NotLike(val) => FindOperator::NotLike(Box::new(val)),
+            Self::ILike(val) => FindOperator::ILike(Box::new(val)),
         }
     }
 }
 
+#[must_use]
+pub fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+
+    for ch in input.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+
+    escaped
+}
+
 pub struct InputRecord<'q, DB: Database>(Vec<(&'q str, Box<dyn Value<'q, DB> + 'q>)>);
 
 impl<'q, DB: Database> InputRecord<'q, DB> {
@@ -262,15 +560,78 @@ pub trait ToInputRecord<'q, DB: Database> {
 }
 
 pub struct OrderBy<C> {
+    pub cols: Vec<OrderByCol<C>>,
+}
+
+impl<C> OrderBy<C> {
+    #[must_use]
+    pub fn new(order: Order, cols: Vec<C>) -> Self {
+        Self {
+            cols: cols
+                .into_iter()
+                .map(|col| OrderByCol {
+                    col,
+                    order,
+                    nulls: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn map_cols<T>(self, mut f: impl FnMut(C) -> T) -> OrderBy<T> {
+        OrderBy {
+            cols: self
+                .cols
+                .into_iter()
+                .map(|order_by_col| OrderByCol {
+                    col: f(order_by_col.col),
+                    order: order_by_col.order,
+                    nulls: order_by_col.nulls,
+                })
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self {
+            cols: self
+                .cols
+                .into_iter()
+                .map(|order_by_col| OrderByCol {
+                    col: order_by_col.col,
+                    order: match order_by_col.order {
+                        Order::Asc => Order::Desc,
+                        Order::Desc => Order::Asc,
+                    },
+                    nulls: order_by_col.nulls.map(|nulls| match nulls {
+                        Nulls::First => Nulls::Last,
+                        Nulls::Last => Nulls::First,
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct OrderByCol<C> {
+    pub col: C,
     pub order: Order,
-    pub cols: Vec<C>,
+    pub nulls: Option<Nulls>,
 }
 
+#[derive(Clone, Copy)]
 pub enum Order {
     Asc,
     Desc,
 }
 
+#[derive(Clone, Copy)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
 pub struct Record<DB: Database> {
     row: Option<DB::Row>,
     map: BTreeMap<String, Box<dyn Any + Send>>,
@@ -291,6 +652,10 @@ impl<DB: Database> Record<DB> {
         }
     }
 
+    pub fn add_col<T: Any + Send>(&mut self, col_name: impl Into<String>, value: T) {
+        self.map.insert(col_name.into(), Box::new(value));
+    }
+
     pub fn col<T: sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Clone + Any>(
         &self,
         col_name: &str,
@@ -303,6 +668,7 @@ impl<DB: Database> Record<DB> {
                 .as_ref()
                 .downcast_ref::<T>()
                 .cloned()
+                .or_else(|| cast_integer(entry.as_ref()))
                 .ok_or_else(|| RecordError::ColumnDecode {
                     index: col_name.into(),
                     source: None,
@@ -320,6 +686,40 @@ impl<DB: Database> Record<DB> {
             Err(RecordError::ColumnNotFound(col_name.into()))
         }
     }
+
+    pub fn try_col<T: sqlx::Type<DB> + for<'d> sqlx::Decode<'d, DB> + Clone + Any>(
+        &self,
+        col_name: &str,
+    ) -> Result<Option<T>, RecordError>
+    where
+        for<'a> &'a str: sqlx::ColumnIndex<DB::Row>,
+    {
+        match self.col(col_name) {
+            Ok(value) => Ok(Some(value)),
+            Err(RecordError::ColumnNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn cast_integer<T: Any>(value: &(dyn Any + Send)) -> Option<T> {
+    let value = *value.downcast_ref::<u64>()?;
+    let mut result = None::<T>;
+    let slot = &mut result as &mut dyn Any;
+
+    macro_rules! try_cast {
+        ($($ty:ty),+) => {
+            $(
+                if let Some(slot) = slot.downcast_mut::<Option<$ty>>() {
+                    *slot = <$ty>::try_from(value).ok();
+                }
+            )+
+        };
+    }
+
+    try_cast!(i8, i16, i32, i64, u8, u16, u32);
+
+    result
 }
 
 impl<DB: Database> Default for Record<DB> {
@@ -342,3 +742,9 @@ pub enum RecordError {
 pub trait FromRecord<DB: Database>: Sized {
     fn from_record(record: &Record<DB>) -> Result<Self, RecordError>;
 }
+
+impl<DB: Database, A: FromRecord<DB>, B: FromRecord<DB>> FromRecord<DB> for (A, B) {
+    fn from_record(record: &Record<DB>) -> Result<Self, RecordError> {
+        Ok((A::from_record(record)?, B::from_record(record)?))
+    }
+}
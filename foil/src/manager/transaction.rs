@@ -0,0 +1,70 @@
+use super::Manager;
+use futures::future::BoxFuture;
+use sqlx::Database;
+use std::error::Error;
+
+pub trait Begin<'m, DB: Database>: Manager<'m, DB> + Sized {
+    type Transaction: Transaction<'m, DB, Error = Self::Error>;
+
+    fn begin(self) -> BoxFuture<'m, Result<Self::Transaction, Self::Error>>;
+
+    fn transaction<F, R, E>(self, callback: F) -> BoxFuture<'m, Result<R, E>>
+    where
+        F: for<'t> FnOnce(&'t mut Self::Transaction) -> BoxFuture<'t, Result<R, E>> + Send + 'm,
+        R: Send + 'm,
+        E: From<Self::Error> + Send + 'm,
+    {
+        Box::pin(run_transaction(self.begin(), callback))
+    }
+}
+
+pub trait Transaction<'c, DB: Database>: Send + Sized + 'c {
+    type Error: Error + Send + Sync + 'static;
+    type Manager<'t>: Manager<'t, DB, Error = Self::Error>
+    where
+        Self: 't;
+    type Nested<'t>: Transaction<'t, DB, Error = Self::Error>
+    where
+        Self: 't;
+
+    fn manager(&mut self) -> Self::Manager<'_>;
+
+    fn begin(&mut self) -> BoxFuture<'_, Result<Self::Nested<'_>, Self::Error>>;
+
+    fn commit(self) -> BoxFuture<'c, Result<(), Self::Error>>;
+
+    fn rollback(self) -> BoxFuture<'c, Result<(), Self::Error>>;
+
+    fn transaction<'t, F, R, E>(&'t mut self, callback: F) -> BoxFuture<'t, Result<R, E>>
+    where
+        F: for<'n> FnOnce(&'n mut Self::Nested<'t>) -> BoxFuture<'n, Result<R, E>> + Send + 't,
+        R: Send + 't,
+        E: From<Self::Error> + Send + 't,
+    {
+        Box::pin(run_transaction(self.begin(), callback))
+    }
+}
+
+async fn run_transaction<'c, DB, T, F, R, E, TE>(
+    begin: BoxFuture<'c, Result<T, TE>>,
+    callback: F,
+) -> Result<R, E>
+where
+    DB: Database,
+    T: Transaction<'c, DB, Error = TE>,
+    F: for<'t> FnOnce(&'t mut T) -> BoxFuture<'t, Result<R, E>>,
+    E: From<TE>,
+{
+    let mut transaction = begin.await?;
+
+    match callback(&mut transaction).await {
+        Ok(value) => {
+            transaction.commit().await?;
+            Ok(value)
+        }
+        Err(err) => {
+            let _ = transaction.rollback().await;
+            Err(err)
+        }
+    }
+}
@@ -0,0 +1,533 @@
+use foil::{manager::MockManager, selector, Create, Delete, Entity, FromRecord, Update};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+#[derive(Debug, Entity, Create, Update, Delete)]
+#[foil(db = "sqlite")]
+struct Character {
+    id: i64,
+    name: String,
+    father_name: Option<String>,
+}
+
+async fn setup() -> MockManager {
+    let mut manager = MockManager::new().await.unwrap();
+    manager
+        .exec_sql(
+            "CREATE TABLE \"character\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, father_name TEXT DEFAULT NULL)",
+        )
+        .await
+        .unwrap();
+
+    Character::insert(
+        &mut manager,
+        vec![
+            CharacterInput {
+                id: foil::Field::Set(1),
+                name: "Legalas",
+                father_name: None,
+            },
+            CharacterInput {
+                id: foil::Field::Set(2),
+                name: "Himmly",
+                father_name: Some("Gloyne"),
+            },
+            CharacterInput {
+                id: foil::Field::Set(3),
+                name: "Aragorn",
+                father_name: Some("Arathorn"),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    manager
+}
+
+fn names(characters: &[Character]) -> Vec<&str> {
+    characters
+        .iter()
+        .map(|character| character.name.as_str())
+        .collect()
+}
+
+mod selector {
+    use super::*;
+
+    #[tokio::test]
+    async fn not_in() {
+        let mut manager = setup().await;
+
+        let characters = Character::find(
+            &mut manager,
+            vec![selector!(CharacterSelector { id: NOT_IN(1, 3) })],
+        )
+        .all()
+        .await
+        .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name", "father_name" FROM "character" WHERE "id" NOT IN (?, ?)"###);
+        assert_debug_snapshot!(names(&characters), @r###"
+        [
+            "Himmly",
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn not_in_vec() {
+        let mut manager = setup().await;
+        let ids = vec![2];
+
+        let characters = Character::find(
+            &mut manager,
+            vec![selector!(CharacterSelector { id: NOT_IN(..ids) })],
+        )
+        .all()
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(names(&characters), @r###"
+        [
+            "Legalas",
+            "Aragorn",
+        ]
+        "###);
+    }
+}
+
+#[derive(Debug, Entity, Create)]
+#[foil(db = "sqlite", has_many(Person, fk = "parent_id", name = "children"))]
+struct Person {
+    id: i64,
+    parent_id: Option<i64>,
+    name: String,
+}
+
+async fn setup_people() -> MockManager {
+    let mut manager = MockManager::new().await.unwrap();
+    manager
+        .exec_sql(
+            "CREATE TABLE \"person\" (id INTEGER PRIMARY KEY, parent_id INTEGER DEFAULT NULL, name TEXT NOT NULL)",
+        )
+        .await
+        .unwrap();
+    manager
+        .exec_sql(
+            "INSERT INTO \"person\" VALUES (1, NULL, 'Arathorn'), (2, 1, 'Aragorn'), (3, NULL, 'Gloyne')",
+        )
+        .await
+        .unwrap();
+
+    manager
+}
+
+#[derive(Debug, Entity, Create)]
+#[foil(db = "sqlite", has_many(City, fk = "kingdom_id"))]
+struct Kingdom {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, Entity, Create)]
+#[foil(db = "sqlite", belongs_to(Kingdom, fk = "kingdom_id"))]
+struct City {
+    id: i64,
+    kingdom_id: i64,
+    name: String,
+}
+
+async fn setup_kingdoms() -> MockManager {
+    let mut manager = MockManager::new().await.unwrap();
+    manager
+        .exec_sql("CREATE TABLE \"kingdom\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    manager
+        .exec_sql(
+            "CREATE TABLE \"city\" (id INTEGER PRIMARY KEY, kingdom_id INTEGER NOT NULL, name TEXT NOT NULL)",
+        )
+        .await
+        .unwrap();
+    manager
+        .exec_sql("INSERT INTO \"kingdom\" VALUES (1, 'Gondor'), (2, 'Rohan')")
+        .await
+        .unwrap();
+    manager
+        .exec_sql(
+            "INSERT INTO \"city\" VALUES (1, 1, 'Minas Tirith'), (2, 1, 'Osgiliath'), (3, 2, 'Edoras')",
+        )
+        .await
+        .unwrap();
+
+    manager
+}
+
+mod relations {
+    use super::*;
+    use foil::manager::impls::mock::MockDialect;
+
+    #[tokio::test]
+    async fn has_many() {
+        let mut manager = setup_kingdoms().await;
+        let kingdom = Kingdom::get(&mut manager, 1).await.unwrap();
+
+        let cities = kingdom.cities(&mut manager).all().await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "kingdom_id", "name" FROM "city" WHERE "kingdom_id" = ?"###);
+        assert_debug_snapshot!(cities.iter().map(|city| city.name.as_str()).collect::<Vec<_>>(), @r###"
+        [
+            "Minas Tirith",
+            "Osgiliath",
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn belongs_to() {
+        let mut manager = setup_kingdoms().await;
+        let city = City::get(&mut manager, 3).await.unwrap();
+
+        let kingdom = city.kingdom(&mut manager).one().await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name" FROM "kingdom" WHERE "id" = ?"###);
+        assert_eq!(kingdom.name, "Rohan");
+    }
+
+    #[tokio::test]
+    async fn load_has_many() {
+        let mut manager = setup_kingdoms().await;
+        let kingdoms = Kingdom::find(&mut manager, vec![KingdomSelector::default()])
+            .all()
+            .await
+            .unwrap();
+
+        let cities = Kingdom::load_cities(&mut manager, &kingdoms).await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "kingdom_id", "name" FROM "city" WHERE "kingdom_id" IN (?, ?)"###);
+        assert_eq!(cities[&1].len(), 2);
+        assert_eq!(cities[&2][0].name, "Edoras");
+    }
+
+    #[tokio::test]
+    async fn load_has_many_chunked() {
+        let mut manager = setup_kingdoms().await.dialect(MockDialect {
+            max_parameters: 1,
+            ..MockDialect::default()
+        });
+        let kingdoms = Kingdom::find(&mut manager, vec![KingdomSelector::default()])
+            .all()
+            .await
+            .unwrap();
+
+        let cities = Kingdom::load_cities(&mut manager, &kingdoms).await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"
+        SELECT "id", "kingdom_id", "name" FROM "city" WHERE "kingdom_id" IN (?);
+        SELECT "id", "kingdom_id", "name" FROM "city" WHERE "kingdom_id" IN (?)
+        "###);
+        assert_eq!(cities[&1].len(), 2);
+        assert_eq!(cities[&2][0].name, "Edoras");
+    }
+
+    #[tokio::test]
+    async fn load_belongs_to() {
+        let mut manager = setup_kingdoms().await;
+        let cities = City::find(&mut manager, vec![CitySelector::default()])
+            .all()
+            .await
+            .unwrap();
+
+        let kingdoms = City::load_kingdom(&mut manager, &cities).await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "id", "name" FROM "kingdom" WHERE "id" IN (?, ?)"###);
+        assert_eq!(kingdoms.len(), 2);
+        assert_eq!(kingdoms[&1].name, "Gondor");
+    }
+
+    #[tokio::test]
+    async fn self_referential() {
+        let mut manager = setup_people().await;
+        let person = Person::get(&mut manager, 1).await.unwrap();
+
+        let children = person.children(&mut manager).all().await.unwrap();
+
+        assert_debug_snapshot!(children.iter().map(|child| child.name.as_str()).collect::<Vec<_>>(), @r###"
+        [
+            "Aragorn",
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn self_referential_exists() {
+        let mut manager = setup_people().await;
+
+        let count = Person::count_where(
+            &mut manager,
+            Person::has_children(vec![PersonSelector::default()]),
+        )
+        .await
+        .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT COUNT (*) AS "cnt" FROM "person" WHERE EXISTS (SELECT 1 FROM "person" AS "t1" WHERE "t1"."parent_id" = "person"."id")"###);
+        assert_eq!(count, 1);
+    }
+}
+
+#[derive(Debug, Entity, Create)]
+#[foil(db = "postgres, sqlite", bulk)]
+struct Ring {
+    id: i64,
+    name: String,
+    bearer: Option<String>,
+}
+
+fn rings() -> Vec<RingInput<'static>> {
+    vec![
+        RingInput {
+            id: foil::Field::Omit,
+            name: "Narya",
+            bearer: Some("Gandalf"),
+        },
+        RingInput {
+            id: foil::Field::Omit,
+            name: "Nenya",
+            bearer: Some("Galadriel"),
+        },
+    ]
+}
+
+mod bulk {
+    use super::*;
+    use foil::{
+        entity::{BulkCreate, CopyError},
+        manager::{InsertQuery, InsertReturningQuery, UnnestInsertQuery},
+    };
+    use sqlx::{PgConnection, Postgres};
+
+    #[allow(dead_code)]
+    async fn copy_rings(conn: &mut PgConnection) -> Result<u64, CopyError> {
+        Ring::copy_in(conn, futures::stream::iter(rings())).await
+    }
+
+    #[test]
+    fn copy_col_names() {
+        assert_eq!(Ring::copy_col_names(), ["name", "bearer"]);
+    }
+
+    #[test]
+    fn unnest_insert() {
+        let inputs = rings();
+        let columns = <Ring as Create<Postgres>>::unnest_columns(&inputs).unwrap();
+
+        let query = UnnestInsertQuery::<Postgres> {
+            insert_returning_query: InsertReturningQuery {
+                insert_query: InsertQuery {
+                    table_name: "ring",
+                    values: vec![],
+                },
+                returning_cols: <Ring as Create<Postgres>>::generated_col_names(),
+                generated_key: None,
+            },
+            columns,
+        };
+
+        assert_snapshot!(query.to_string(), @r###"INSERT INTO "ring" ("name", "bearer") SELECT "name", "bearer" FROM UNNEST($1, $2) WITH ORDINALITY AS "unnest" ("name", "bearer", "ordinality") ORDER BY "ordinality" RETURNING "id""###);
+    }
+
+    #[tokio::test]
+    async fn sqlite_create_many() {
+        let mut manager = MockManager::new().await.unwrap();
+        manager
+            .exec_sql(
+                "CREATE TABLE \"ring\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, bearer TEXT DEFAULT NULL)",
+            )
+            .await
+            .unwrap();
+
+        let rings = Ring::create_many(&mut manager, rings()).await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "ring" ("name", "bearer") VALUES (?, ?), (?, ?) RETURNING "id""###);
+        assert_debug_snapshot!(rings, @r###"
+        [
+            Ring {
+                id: 1,
+                name: "Narya",
+                bearer: Some(
+                    "Gandalf",
+                ),
+            },
+            Ring {
+                id: 2,
+                name: "Nenya",
+                bearer: Some(
+                    "Galadriel",
+                ),
+            },
+        ]
+        "###);
+    }
+}
+
+#[derive(Debug, Entity, Create)]
+#[foil(
+    db = "sqlite",
+    many_to_many(Ring, fk = "hobbit_id", join_table = "bearing", target_fk = "ring_id")
+)]
+struct Hobbit {
+    id: i64,
+    name: String,
+}
+
+async fn setup_hobbits() -> (MockManager, Hobbit) {
+    let mut manager = MockManager::new().await.unwrap();
+    manager
+        .exec_sql("CREATE TABLE \"hobbit\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    manager
+        .exec_sql(
+            "CREATE TABLE \"ring\" (id INTEGER PRIMARY KEY, name TEXT NOT NULL, bearer TEXT DEFAULT NULL)",
+        )
+        .await
+        .unwrap();
+    manager
+        .exec_sql("CREATE TABLE \"bearing\" (hobbit_id INTEGER NOT NULL, ring_id INTEGER NOT NULL)")
+        .await
+        .unwrap();
+    manager
+        .exec_sql("INSERT INTO \"hobbit\" VALUES (1, 'Frodo')")
+        .await
+        .unwrap();
+    manager
+        .exec_sql("INSERT INTO \"ring\" VALUES (1, 'The One Ring', 'Frodo'), (2, 'Narya', 'Gandalf'), (3, 'Nenya', 'Galadriel')")
+        .await
+        .unwrap();
+    let hobbit = Hobbit::get(&mut manager, 1).await.unwrap();
+
+    (manager, hobbit)
+}
+
+async fn ring_names(manager: &mut MockManager, hobbit: &Hobbit) -> Vec<String> {
+    hobbit
+        .rings(manager)
+        .all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|ring| ring.name)
+        .collect()
+}
+
+mod many_to_many {
+    use super::*;
+    use foil::manager::{Begin, Transaction};
+
+    #[tokio::test]
+    async fn attach_and_detach() {
+        let (mut manager, hobbit) = setup_hobbits().await;
+
+        hobbit.attach_rings(&mut manager, vec![1, 3]).await.unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"INSERT INTO "bearing" ("hobbit_id", "ring_id") VALUES (?, ?), (?, ?)"###);
+        assert_debug_snapshot!(ring_names(&mut manager, &hobbit).await, @r###"
+        [
+            "The One Ring",
+            "Nenya",
+        ]
+        "###);
+
+        let rows_affected = hobbit.detach_rings(&mut manager, vec![3]).await.unwrap();
+
+        assert_eq!(rows_affected, 1);
+        assert_debug_snapshot!(ring_names(&mut manager, &hobbit).await, @r###"
+        [
+            "The One Ring",
+        ]
+        "###);
+    }
+
+    #[tokio::test]
+    async fn sync() {
+        let (mut manager, hobbit) = setup_hobbits().await;
+        hobbit.attach_rings(&mut manager, vec![1, 3]).await.unwrap();
+
+        let mut transaction = (&mut manager).begin().await.unwrap();
+        hobbit
+            .sync_rings(&mut transaction, vec![1, 2])
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        assert_debug_snapshot!(ring_names(&mut manager, &hobbit).await, @r###"
+        [
+            "The One Ring",
+            "Narya",
+        ]
+        "###);
+    }
+}
+
+#[derive(Debug, FromRecord)]
+#[foil(db = "sqlite")]
+struct Father {
+    father_name: Option<String>,
+}
+
+#[derive(Debug, FromRecord)]
+#[foil(db = "sqlite")]
+struct Lineage {
+    #[foil(rename = "name")]
+    character: String,
+    #[foil(default)]
+    title: String,
+    #[foil(flatten)]
+    father: Father,
+}
+
+mod from_record {
+    use super::*;
+    use foil::entity::FindOptions;
+
+    #[tokio::test]
+    async fn project() {
+        let mut manager = setup().await;
+
+        let lineages: Vec<Lineage> = Character::project(
+            &mut manager,
+            vec![selector!(CharacterSelector { id: 2 })],
+            vec![CharacterCol::Name, CharacterCol::FatherName],
+            FindOptions::default(),
+        )
+        .all()
+        .await
+        .unwrap();
+
+        assert_snapshot!(manager.last_statement().unwrap(), @r###"SELECT "name", "father_name" FROM "character" WHERE "id" = ?"###);
+        assert_eq!(lineages.len(), 1);
+        assert_eq!(lineages[0].character, "Himmly");
+        assert_eq!(lineages[0].title, "");
+        assert_eq!(lineages[0].father.father_name.as_deref(), Some("Gloyne"));
+    }
+}
+
+mod dbs {
+    use super::*;
+    use sqlx::{Postgres, Sqlite};
+
+    fn table_name<DB: sqlx::Database, E: Entity<DB>>() -> &'static str {
+        E::table_name()
+    }
+
+    #[test]
+    fn restricted() {
+        assert_eq!(table_name::<Sqlite, Character>(), "character");
+    }
+
+    #[test]
+    fn multiple() {
+        assert_eq!(table_name::<Postgres, Ring>(), "ring");
+        assert_eq!(table_name::<Sqlite, Ring>(), "ring");
+    }
+}